@@ -0,0 +1,43 @@
+//! A local stand-in of the answer endpoint of the puzzle website, used by default by the `submit`
+//! subcommand. Answers are judged against an expected answers file.
+//!
+//! Usage: `cargo run --example mock_server [ADDRESS] [EXPECTED_ANSWERS] [MIN_INTERVAL_SECS]`,
+//! listening on 127.0.0.1:8021 and reading aoc_inputs/expected_answers by default.
+
+#![deny(clippy::all)]
+
+mod server;
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc_2021_rs::input::inputs_directory;
+use aoc_2021_rs::submit::DEFAULT_ENDPOINT;
+use aoc_2021_rs::verify::ExpectedAnswers;
+use aoc_2021_rs::Error;
+use server::MockServer;
+
+fn main() -> Result<(), Error> {
+    let mut args = std::env::args().skip(1);
+    let address = args
+        .next()
+        .unwrap_or_else(|| DEFAULT_ENDPOINT.trim_start_matches("http://").to_string());
+    let expected_path = args
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| ExpectedAnswers::default_path(&inputs_directory()));
+    let min_interval = args
+        .next()
+        .and_then(|seconds| seconds.parse().ok())
+        .map_or(Duration::ZERO, Duration::from_secs);
+
+    let server = MockServer::bind(&address, ExpectedAnswers::load(&expected_path)?)?
+        .with_min_interval(min_interval);
+    println!(
+        "Judging answers of '{}' on http://{}",
+        expected_path.display(),
+        server.local_addr()?
+    );
+
+    server.serve(None)
+}
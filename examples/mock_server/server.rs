@@ -0,0 +1,180 @@
+//! The answer judging server of the `mock_server` example, shared with the submission tests.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use aoc_2021_rs::verify::ExpectedAnswers;
+use aoc_2021_rs::{Error, Part};
+
+fn invalid_data<S: Into<String>>(message: S) -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into()).into()
+}
+
+/// Decode a form value.
+pub fn form_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let escaped = bytes
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match escaped {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        index += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// A local stand-in of the answer endpoint of the puzzle website, judging answers against the
+/// expected answers, for offline practice and tests.
+///
+/// Numeric answers are told too high or too low, and an answer submitted less than the minimal
+/// interval after the previous one is rate limited.
+#[derive(Debug)]
+pub struct MockServer {
+    listener: TcpListener,
+    expected_answers: ExpectedAnswers,
+    min_interval: Duration,
+}
+
+impl MockServer {
+    /// Listen on provided address, such as `127.0.0.1:8021`, without rate limit.
+    pub fn bind(address: &str, expected_answers: ExpectedAnswers) -> Result<Self, Error> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            expected_answers,
+            min_interval: Duration::ZERO,
+        })
+    }
+
+    /// Rate limit answers submitted less than provided interval after the previous one.
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Handle up to `requests` requests, or every request if None.
+    pub fn serve(&self, requests: Option<usize>) -> Result<(), Error> {
+        let mut last_submission: Option<Instant> = None;
+
+        for stream in self
+            .listener
+            .incoming()
+            .take(requests.unwrap_or(usize::MAX))
+        {
+            let mut stream = stream?;
+            let (status, message) = match self.judge(&mut stream, &mut last_submission) {
+                Ok(message) => ("200 OK", message),
+                Err(err) => ("404 Not Found", err.to_string()),
+            };
+            let body = format!(
+                "<html><body><main><article><p>{}</p></article></main></body></html>\n",
+                message
+            );
+
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a request and return the message answering it.
+    fn judge(
+        &self,
+        stream: &mut TcpStream,
+        last_submission: &mut Option<Instant>,
+    ) -> Result<String, Error> {
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let request_line = request_line.trim_end();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse::<usize>().unwrap_or(0);
+                }
+            }
+        }
+
+        let day = request_line
+            .strip_prefix("POST /2021/day/")
+            .and_then(|rest| rest.split_once("/answer "))
+            .and_then(|(day, _)| day.parse::<u8>().ok())
+            .ok_or_else(|| invalid_data(format!("unknown request '{}'", request_line)))?;
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+
+        let mut part = None;
+        let mut answer = None;
+        for field in String::from_utf8_lossy(&body).split('&') {
+            match field.split_once('=') {
+                Some(("level", level)) => part = level.parse::<Part>().ok(),
+                Some(("answer", value)) => answer = Some(form_decode(value)),
+                _ => (),
+            }
+        }
+        let (part, answer) = part
+            .zip(answer)
+            .ok_or_else(|| invalid_data("missing level or answer"))?;
+
+        let now = Instant::now();
+        if let Some(last) = *last_submission {
+            let elapsed = now.duration_since(last);
+            if elapsed < self.min_interval {
+                let wait = (self.min_interval - elapsed).as_secs().max(1);
+                return Ok(format!(
+                    "You gave an answer too recently; you have to wait after submitting an \
+                     answer before trying again. You have {}s left to wait.",
+                    wait
+                ));
+            }
+        }
+        *last_submission = Some(now);
+
+        let expected = match self.expected_answers.get(day, part) {
+            Some(expected) => expected,
+            None => return Ok(String::from("This stand-in does not know the answer.")),
+        };
+        if answer == expected {
+            return Ok(String::from("That's the right answer! You got a star."));
+        }
+
+        let hint = match (answer.parse::<u128>(), expected.parse::<u128>()) {
+            (Ok(value), Ok(expected)) if value > expected => " your answer is too high.",
+            (Ok(value), Ok(expected)) if value < expected => " your answer is too low.",
+            _ => "",
+        };
+        Ok(format!("That's not the right answer;{}", hint))
+    }
+}
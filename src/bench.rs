@@ -0,0 +1,118 @@
+//! This module measures the duration of daily solutions.
+//! Input parsing and each part are timed separately over several iterations.
+
+use std::time::{Duration, Instant};
+
+use crate::input::InputSource;
+use crate::{DailySolution, Error, Part};
+
+/// Summary of the durations measured for a single phase.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Statistics {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Statistics {
+    /// Summarize provided samples, or return None if there is no sample.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted_samples = samples.to_vec();
+        sorted_samples.sort();
+
+        let middle = sorted_samples.len() / 2;
+        let median = if sorted_samples.len().is_multiple_of(2) {
+            (sorted_samples[middle - 1] + sorted_samples[middle]) / 2
+        } else {
+            sorted_samples[middle]
+        };
+        let mean = sorted_samples.iter().sum::<Duration>() / sorted_samples.len() as u32;
+
+        Some(Self {
+            min: sorted_samples[0],
+            median,
+            mean,
+        })
+    }
+}
+
+/// Measured durations of a daily solution.
+#[derive(Clone, Debug)]
+pub struct BenchReport {
+    pub day: u8,
+    pub iterations: usize,
+    pub parsing: Statistics,
+    pub parts: Vec<(Part, Statistics)>,
+}
+
+/// Run the solution `iterations` times over provided input, timing input parsing and each
+/// requested part separately. At least one iteration is always run.
+///
+/// The input is read only once before measures, so that parsing durations do not include
+/// reading the source.
+pub fn bench_solution(
+    solution: &dyn DailySolution,
+    source: &InputSource,
+    parts: &[Part],
+    iterations: usize,
+) -> Result<BenchReport, Error> {
+    let iterations = iterations.max(1);
+    let content = source.read_to_string()?;
+    let mut parsing_samples = Vec::with_capacity(iterations);
+    let mut part_samples = vec![Vec::with_capacity(iterations); parts.len()];
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let daily_input = solution.parse_input(&mut content.as_bytes())?;
+        parsing_samples.push(start.elapsed());
+
+        for (&part, samples) in parts.iter().zip(part_samples.iter_mut()) {
+            let start = Instant::now();
+            daily_input.solve(part)?;
+            samples.push(start.elapsed());
+        }
+    }
+
+    Ok(BenchReport {
+        day: solution.day_number(),
+        iterations,
+        parsing: Statistics::from_samples(&parsing_samples).unwrap(),
+        parts: parts
+            .iter()
+            .zip(part_samples.iter())
+            .map(|(&part, samples)| (part, Statistics::from_samples(samples).unwrap()))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod test_bench {
+    use std::time::Duration;
+
+    use super::Statistics;
+
+    #[test]
+    fn test_statistics() {
+        let samples: Vec<Duration> = [4, 1, 3, 8]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        let statistics = Statistics::from_samples(&samples).unwrap();
+
+        assert_eq!(statistics.min, Duration::from_millis(1));
+        assert_eq!(statistics.median, Duration::from_micros(3500));
+        assert_eq!(statistics.mean, Duration::from_millis(4));
+
+        let statistics = Statistics::from_samples(&samples[..3]).unwrap();
+        assert_eq!(statistics.median, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_no_samples() {
+        assert!(Statistics::from_samples(&[]).is_none());
+    }
+}
@@ -0,0 +1,176 @@
+//! This module manages the daily input files stored into an inputs directory, or into one of its
+//! named input sets, following the naming convention of the [`input`](crate::input) module.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::input::{input_file_name, is_valid_set_name};
+use crate::Error;
+
+/// The day whose input is stored into provided file name, if it follows the naming convention.
+fn input_file_day(file_name: &str) -> Option<u8> {
+    let day = file_name.strip_prefix("daily_input_")?;
+
+    if day.starts_with('0') {
+        return None;
+    }
+    day.parse().ok()
+}
+
+/// The input file of a day within an inputs directory.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InputEntry {
+    pub day: u8,
+    pub path: PathBuf,
+    /// The size of the file in bytes, or None if the file does not exist.
+    pub size: Option<u64>,
+}
+
+/// List the input files of provided days, along with every other input file found into the
+/// directory, sorted by day.
+pub fn list_inputs<I>(directory: &Path, days: I) -> Result<Vec<InputEntry>, Error>
+where
+    I: IntoIterator<Item = u8>,
+{
+    let mut day_numbers: Vec<u8> = days.into_iter().collect();
+
+    match fs::read_dir(directory) {
+        Ok(dir_entries) => {
+            for dir_entry in dir_entries {
+                let dir_entry = dir_entry?;
+                if let Some(day) = dir_entry.file_name().to_str().and_then(input_file_day) {
+                    if dir_entry.file_type()?.is_file() {
+                        day_numbers.push(day);
+                    }
+                }
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+        Err(err) => return Err(err.into()),
+    }
+    day_numbers.sort_unstable();
+    day_numbers.dedup();
+
+    Ok(day_numbers
+        .into_iter()
+        .map(|day| {
+            let path = directory.join(input_file_name(day));
+            let size = fs::metadata(&path).ok().map(|metadata| metadata.len());
+
+            InputEntry { day, path, size }
+        })
+        .collect())
+}
+
+/// The named input sets stored into provided inputs directory, sorted by name.
+pub fn input_sets(inputs_directory: &Path) -> Result<Vec<String>, Error> {
+    let mut sets = Vec::new();
+
+    for dir_entry in fs::read_dir(inputs_directory)? {
+        let dir_entry = dir_entry?;
+        if !dir_entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(name) = dir_entry.file_name().to_str() {
+            if is_valid_set_name(name) {
+                sets.push(name.to_string());
+            }
+        }
+    }
+    sets.sort();
+
+    Ok(sets)
+}
+
+/// Copy a file as the input of provided day into a directory, which is created if needed.
+/// An existing input file is only replaced when `overwrite` is set, and is left untouched when
+/// it already is the imported file.
+pub fn import_input(
+    file: &Path,
+    directory: &Path,
+    day: u8,
+    overwrite: bool,
+) -> Result<PathBuf, Error> {
+    let path = directory.join(input_file_name(day));
+
+    if !overwrite && path.exists() {
+        return Err(Error::AlreadyExists(format!(
+            "input file '{}'",
+            path.display()
+        )));
+    }
+
+    if path.exists() && fs::canonicalize(file)? == fs::canonicalize(&path)? {
+        return Ok(path);
+    }
+
+    fs::create_dir_all(directory)?;
+    fs::copy(file, &path)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test_cache {
+    use std::fs;
+
+    use super::{import_input, input_file_day, input_sets, list_inputs, InputEntry};
+
+    #[test]
+    fn test_input_file_day() {
+        assert_eq!(input_file_day("daily_input_7"), Some(7));
+        assert_eq!(input_file_day("daily_input_12"), Some(12));
+        assert_eq!(input_file_day("daily_input_07"), None);
+        assert_eq!(input_file_day("daily_input_"), None);
+        assert_eq!(input_file_day("expected_answers"), None);
+    }
+
+    #[test]
+    fn test_manage_inputs() {
+        let directory = std::env::temp_dir().join(format!("aoc_cache_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(directory.join("alice")).unwrap();
+        fs::create_dir_all(directory.join(".hidden")).unwrap();
+        fs::write(directory.join("daily_input_9"), "2199943210\n").unwrap();
+        fs::write(directory.join("download.txt"), "199\n200\n").unwrap();
+
+        let path = import_input(&directory.join("download.txt"), &directory, 1, false).unwrap();
+        assert_eq!(path, directory.join("daily_input_1"));
+        assert!(import_input(&directory.join("download.txt"), &directory, 1, false).is_err());
+        import_input(&directory.join("daily_input_9"), &directory, 1, true).unwrap();
+        import_input(&directory.join("daily_input_9"), &directory, 9, true).unwrap();
+        import_input(
+            &directory.join(".hidden/../daily_input_1"),
+            &directory,
+            1,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(
+            list_inputs(&directory, [1, 2]).unwrap(),
+            vec![
+                InputEntry {
+                    day: 1,
+                    path: directory.join("daily_input_1"),
+                    size: Some(11),
+                },
+                InputEntry {
+                    day: 2,
+                    path: directory.join("daily_input_2"),
+                    size: None,
+                },
+                InputEntry {
+                    day: 9,
+                    path: directory.join("daily_input_9"),
+                    size: Some(11),
+                },
+            ]
+        );
+        assert_eq!(list_inputs(&directory.join("bob"), [3]).unwrap().len(), 1);
+        assert_eq!(input_sets(&directory).unwrap(), vec![String::from("alice")]);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
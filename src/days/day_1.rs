@@ -1,51 +1,354 @@
 //! This is the day 1 module.
 //! The instructions are available here: https://adventofcode.com/2021/day/1
 
-use std::fs;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
 
-use crate::{DailySolution, Error};
+use crate::{log, parse_number, DailyInput, DailySolution, Error, ParseError, Part, PartAnswer};
 
 pub struct Solution;
 
+impl Solution {
+    pub const DAY_NUMBER: u8 = 1;
+
+    /// Parse the input with provided handling of bad readings, returning the tolerated ones.
+    pub fn parse_input_with(
+        &self,
+        reader: &mut dyn BufRead,
+        mode: ParseMode,
+    ) -> Result<(Box<dyn DailyInput>, Vec<Anomaly>), Error> {
+        let mut single = IncreaseCounter::new(1);
+        let mut windowed = IncreaseCounter::new(3);
+        let mut count = 0;
+        let anomalies = read_depths(reader, mode, |depth| {
+            single.push(depth);
+            windowed.push(depth);
+            count += 1;
+        })?;
+
+        let depths = Depths {
+            single_increases: single.increases(),
+            windowed_increases: windowed.increases(),
+            count,
+        };
+        Ok((Box::new(depths), anomalies))
+    }
+}
+
 impl DailySolution for Solution {
-    const DAY_NUMBER: u8 = 1;
-
-    fn run_solution<P>(input_path: P) -> Result<(), Error>
-    where
-        P: AsRef<Path>,
-    {
-        let input_lines: Vec<usize> = BufReader::new(fs::File::open(input_path)?)
-            .lines()
-            .map(|l| l.unwrap().parse::<usize>().unwrap())
-            .collect();
+    fn day_number(&self) -> u8 {
+        Self::DAY_NUMBER
+    }
+
+    fn title(&self) -> &'static str {
+        "Sonar Sweep"
+    }
+
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error> {
+        let (depths, _) = self.parse_input_with(reader, ParseMode::Strict)?;
+
+        Ok(depths)
+    }
+}
+
+/// The day 1 solution tolerating the bad readings of its input with the wrapped mode.
+pub struct LenientSolution(pub ParseMode);
+
+impl DailySolution for LenientSolution {
+    fn day_number(&self) -> u8 {
+        Solution::DAY_NUMBER
+    }
+
+    fn title(&self) -> &'static str {
+        Solution.title()
+    }
+
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error> {
+        let (depths, _) = Solution.parse_input_with(reader, self.0)?;
+
+        Ok(depths)
+    }
+}
+
+static SKIPPING_SOLUTION: LenientSolution = LenientSolution(ParseMode::Skip);
+static INTERPOLATING_SOLUTION: LenientSolution = LenientSolution(ParseMode::Interpolate);
+
+/// The day 1 solution handling the bad readings of its input with provided mode.
+pub fn solution(mode: ParseMode) -> &'static dyn DailySolution {
+    match mode {
+        ParseMode::Strict => &Solution,
+        ParseMode::Skip => &SKIPPING_SOLUTION,
+        ParseMode::Interpolate => &INTERPOLATING_SOLUTION,
+    }
+}
+
+/// The increases counted over the depth measurements of the sonar sweep report while reading
+/// them, so that the measurements themselves are not stored.
+struct Depths {
+    single_increases: usize,
+    windowed_increases: usize,
+    count: usize,
+}
 
-        let part_1_result = part_1(&input_lines);
-        println!("Depth measurement increase count: {}", part_1_result);
+impl DailyInput for Depths {
+    fn solve(&self, part: Part) -> Result<PartAnswer, Error> {
+        Ok(match part {
+            Part::One => PartAnswer::new(
+                part,
+                "Depth measurement increase count",
+                self.single_increases,
+            ),
+            Part::Two => PartAnswer::new(
+                part,
+                "Windowed measurements increase count",
+                self.windowed_increases,
+            ),
+        })
+    }
 
-        let part_2_result = part_2(&input_lines);
-        println!("Windowed measurements increase count: {}", part_2_result);
-        Ok(())
+    fn record_count(&self) -> usize {
+        self.count
     }
 }
 
-fn count_increases(items: &[usize]) -> usize {
-    items.windows(2).filter(|x| x[0] < x[1]).count()
+/// Read the depth measurements of a report one line at a time, without storing them.
+pub fn depths<R>(reader: R) -> impl Iterator<Item = Result<usize, Error>>
+where
+    R: BufRead,
+{
+    reader
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_number(Solution::DAY_NUMBER, index + 1, 1, line?.trim()))
 }
 
-fn part_1(lines: &[usize]) -> usize {
-    count_increases(lines)
+/// How the bad readings of a depth report, such as blank lines or negative depths, are handled.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ParseMode {
+    /// Fail at the first bad reading.
+    #[default]
+    Strict,
+    /// Ignore bad readings.
+    Skip,
+    /// Replace bad readings by interpolating the closest valid readings around them.
+    Interpolate,
 }
 
-fn part_2(lines: &[usize]) -> usize {
-    let windows: Vec<usize> = lines.windows(3).map(|x| x.iter().sum()).collect();
-    count_increases(&windows)
+impl FromStr for ParseMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "strict" => Ok(Self::Strict),
+            "skip" => Ok(Self::Skip),
+            "interpolate" => Ok(Self::Interpolate),
+            _ => Err(format!(
+                "unknown parse mode '{}', expected strict, skip or interpolate",
+                s
+            )),
+        }
+    }
+}
+
+/// A bad reading tolerated by a lenient parse mode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Anomaly {
+    /// The error of the reading in strict mode.
+    pub error: ParseError,
+    /// The depth used instead of the reading, or None if the reading is skipped.
+    pub replacement: Option<usize>,
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {} '{}', ",
+            self.error.line, self.error.kind, self.error.text
+        )?;
+        match self.replacement {
+            Some(depth) => write!(f, "replaced by {}", depth),
+            None => f.write_str("skipped"),
+        }
+    }
+}
+
+/// Read the depth measurements of a report one line at a time, handling bad readings with
+/// provided mode, and return the tolerated bad readings, which are also logged as warnings.
+/// Input errors always fail.
+///
+/// When interpolating, bad readings are kept until the next valid reading, and readings before
+/// the first or after the last valid one are replaced by the closest valid reading.
+pub fn read_depths<R, F>(reader: R, mode: ParseMode, mut on_depth: F) -> Result<Vec<Anomaly>, Error>
+where
+    R: BufRead,
+    F: FnMut(usize),
+{
+    let mut anomalies = Vec::new();
+    let mut previous = None;
+    let mut pending = Vec::new();
+
+    for depth in depths(reader) {
+        let error = match depth {
+            Ok(depth) => {
+                let gap = pending.len() + 1;
+                for (position, error) in pending.drain(..).enumerate() {
+                    let replacement = match previous {
+                        Some(previous) => interpolate(previous, depth, position + 1, gap),
+                        None => depth,
+                    };
+                    report_anomaly(&mut anomalies, error, Some(replacement));
+                    on_depth(replacement);
+                }
+                on_depth(depth);
+                previous = Some(depth);
+                continue;
+            }
+            Err(Error::ParseError(error)) if mode != ParseMode::Strict => error,
+            Err(err) => return Err(err),
+        };
+
+        match mode {
+            ParseMode::Interpolate => pending.push(error),
+            _ => report_anomaly(&mut anomalies, error, None),
+        }
+    }
+    for error in pending {
+        report_anomaly(&mut anomalies, error, previous);
+        if let Some(previous) = previous {
+            on_depth(previous);
+        }
+    }
+
+    Ok(anomalies)
+}
+
+/// The depth at provided position of a gap between two readings, rounded to the nearest unit.
+fn interpolate(start: usize, end: usize, position: usize, gap: usize) -> usize {
+    (start * (gap - position) + end * position + gap / 2) / gap
+}
+
+fn report_anomaly(anomalies: &mut Vec<Anomaly>, error: ParseError, replacement: Option<usize>) {
+    let anomaly = Anomaly { error, replacement };
+    log::warn(Some(Solution::DAY_NUMBER), anomaly.to_string());
+    anomalies.push(anomaly);
+}
+
+/// The change of the sum of a sliding window from the previous window, along with its magnitude.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WindowChange {
+    Increase(usize),
+    Decrease(usize),
+    Plateau,
+}
+
+/// A sliding window over depth measurements, fed one at a time.
+///
+/// Two consecutive windows share every measurement but the first of the former and the last of
+/// the latter, so that windows are compared by the measurement leaving the window and the one
+/// entering it, without summing them again. Only the measurements of the current window are kept.
+#[derive(Clone, Debug)]
+pub struct SlidingWindow {
+    measurements: VecDeque<usize>,
+    size: usize,
+    sum: u128,
+}
+
+impl SlidingWindow {
+    /// A window of provided size, which must not be 0.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "window size must not be 0");
+
+        Self {
+            measurements: VecDeque::with_capacity(size),
+            size,
+            sum: 0,
+        }
+    }
+
+    /// Add the next measurement, returning the change from the previous window if the window was
+    /// already complete.
+    pub fn push(&mut self, depth: usize) -> Option<WindowChange> {
+        let mut change = None;
+        if self.measurements.len() == self.size {
+            let leaving = self.measurements.pop_front().unwrap();
+            self.sum -= leaving as u128;
+            change = Some(match depth.cmp(&leaving) {
+                Ordering::Greater => WindowChange::Increase(depth - leaving),
+                Ordering::Less => WindowChange::Decrease(leaving - depth),
+                Ordering::Equal => WindowChange::Plateau,
+            });
+        }
+        self.measurements.push_back(depth);
+        self.sum += depth as u128;
+
+        change
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the window holds as many measurements as its size.
+    pub fn is_complete(&self) -> bool {
+        self.measurements.len() == self.size
+    }
+
+    /// The sum of the measurements of the window.
+    pub fn sum(&self) -> u128 {
+        self.sum
+    }
+}
+
+/// Count the increases of the sums of sliding windows over depth measurements, fed one at a time.
+#[derive(Clone, Debug)]
+pub struct IncreaseCounter {
+    window: SlidingWindow,
+    increases: usize,
+}
+
+impl IncreaseCounter {
+    /// A counter over windows of provided size, which must not be 0.
+    pub fn new(size: usize) -> Self {
+        Self {
+            window: SlidingWindow::new(size),
+            increases: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: usize) {
+        if let Some(WindowChange::Increase(_)) = self.window.push(depth) {
+            self.increases += 1;
+        }
+    }
+
+    /// The number of increases counted so far.
+    pub fn increases(&self) -> usize {
+        self.increases
+    }
+}
+
+/// Count the increases of the sums of sliding windows of provided size over depth measurements.
+pub fn count_increases<I>(depths: I, window: usize) -> usize
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut counter = IncreaseCounter::new(window);
+    depths.into_iter().for_each(|depth| counter.push(depth));
+
+    counter.increases()
 }
 
 #[cfg(test)]
 mod test_day {
-    use super::{part_1, part_2};
+    use super::{
+        count_increases, read_depths, solution, IncreaseCounter, ParseMode, SlidingWindow,
+        Solution, WindowChange,
+    };
+    use crate::{expect_parse_error, Answer, DailySolution, ParseErrorKind, Part};
 
     /// Sample lines from guidelines
     const TEST_LINES: &str = "199
@@ -59,23 +362,127 @@ mod test_day {
 260
 263";
 
+    crate::example_tests!(super::Solution, {
+        test_guidelines: TEST_LINES => [7, 5],
+    });
+
     #[test]
-    fn test_part_1() {
-        let input_lines: Vec<usize> = TEST_LINES
-            .lines()
-            .map(|l| l.parse::<usize>().unwrap())
-            .collect();
+    fn test_count_increases() {
+        let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
 
-        assert_eq!(part_1(&input_lines), 7);
+        assert_eq!(count_increases(depths, 1), 7);
+        assert_eq!(count_increases(depths, 3), 5);
+        assert_eq!(count_increases(depths, 9), 1);
+        assert_eq!(count_increases(depths, 10), 0);
+        assert_eq!(count_increases(depths.into_iter().cycle().take(100), 10), 0);
+        assert_eq!(count_increases([], 3), 0);
+
+        let mut counter = IncreaseCounter::new(2);
+        [3, 1, 4, 1, 5]
+            .into_iter()
+            .for_each(|depth| counter.push(depth));
+        assert_eq!(counter.increases(), 2);
     }
 
     #[test]
-    fn test_part_2() {
-        let input_lines: Vec<usize> = TEST_LINES
-            .lines()
-            .map(|l| l.parse::<usize>().unwrap())
+    fn test_sliding_window() {
+        let mut window = SlidingWindow::new(2);
+        let changes: Vec<Option<WindowChange>> = [3, 1, 4, 1, 1]
+            .into_iter()
+            .map(|depth| window.push(depth))
             .collect();
 
-        assert_eq!(part_2(&input_lines), 5);
+        assert_eq!(
+            changes,
+            vec![
+                None,
+                None,
+                Some(WindowChange::Increase(1)),
+                Some(WindowChange::Plateau),
+                Some(WindowChange::Decrease(3))
+            ]
+        );
+        assert!(window.is_complete());
+        assert_eq!(window.sum(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must not be 0")]
+    fn test_empty_window() {
+        IncreaseCounter::new(0);
+    }
+
+    #[test]
+    fn test_lenient_depths() {
+        const BAD_LINES: &str = "199\n\n-208\n 210 \n200\nabc\n";
+        let read = |mode| {
+            let mut depths = Vec::new();
+            read_depths(BAD_LINES.as_bytes(), mode, |depth| depths.push(depth))
+                .map(|anomalies| (depths, anomalies))
+        };
+
+        assert!(read(ParseMode::Strict).is_err());
+
+        let (depths, anomalies) = read(ParseMode::Skip).unwrap();
+        assert_eq!(depths, vec![199, 210, 200]);
+        assert_eq!(
+            anomalies.iter().map(|x| x.error.line).collect::<Vec<_>>(),
+            vec![2, 3, 6]
+        );
+        assert_eq!(
+            anomalies[1].to_string(),
+            "line 3: invalid number '-208', skipped"
+        );
+
+        let (depths, anomalies) = read(ParseMode::Interpolate).unwrap();
+        assert_eq!(depths, vec![199, 203, 206, 210, 200, 200]);
+        assert_eq!(
+            anomalies[0].to_string(),
+            "line 2: invalid number '', replaced by 203"
+        );
+        assert_eq!(anomalies[2].replacement, Some(200));
+    }
+
+    #[test]
+    fn test_parse_input_with() {
+        let (input, anomalies) = Solution
+            .parse_input_with(&mut "x\n199\n200\n".as_bytes(), ParseMode::Interpolate)
+            .unwrap();
+        assert_eq!(input.record_count(), 3);
+        assert_eq!(anomalies[0].replacement, Some(199));
+
+        let (input, anomalies) = Solution
+            .parse_input_with(&mut "x\n\n".as_bytes(), ParseMode::Interpolate)
+            .unwrap();
+        assert_eq!(input.record_count(), 0);
+        assert_eq!(anomalies.len(), 2);
+        assert_eq!("Skip".parse::<ParseMode>(), Ok(ParseMode::Skip));
+    }
+
+    #[test]
+    fn test_solution_modes() {
+        const BAD_LINES: &str = "199\n\n210\n200\n";
+        let run = |mode| solution(mode).run_str(BAD_LINES, &[Part::One]);
+
+        assert!(run(ParseMode::Strict).is_err());
+        assert!(Solution.run_str(BAD_LINES, &[Part::One]).is_err());
+        assert_eq!(run(ParseMode::Skip).unwrap()[0].answer, Answer::Number(1));
+        assert_eq!(
+            run(ParseMode::Interpolate).unwrap()[0].answer,
+            Answer::Number(2)
+        );
+        assert_eq!(solution(ParseMode::Skip).day_number(), 1);
+    }
+
+    #[test]
+    fn test_invalid_depth() {
+        let err = expect_parse_error(read_depths(
+            "199\n200\n-208\n".as_bytes(),
+            ParseMode::Strict,
+            |_| (),
+        ));
+        assert_eq!(err.line, 3);
+        assert_eq!(err.text, "-208");
+        assert_eq!(err.kind, ParseErrorKind::InvalidNumber);
     }
 }
@@ -1,84 +1,442 @@
 //! This is the day 2 module.
 //! The instructions are available here: https://adventofcode.com/2021/day/2
+//!
+//! The planned course is parsed as a list of [`Command`]s, which are then run by an
+//! [`Interpreter`], each movement model of the puzzle being an interpreter. Beyond the puzzle
+//! commands, units may be negative, `back <units>` moves backward and `hold` keeps the submarine
+//! in place for a step. The trajectory followed by an interpreter can be exported as CSV or as an
+//! SVG plot.
 
-use std::fs;
-use std::path::Path;
+use std::fmt::{Display, Formatter, Write};
+use std::io::BufRead;
+use std::str::FromStr;
 
-use regex::RegexBuilder;
-
-use crate::{DailySolution, Error};
+use crate::{
+    parse_number, read_content, split_fields, Answer, DailyInput, DailySolution, Error, ParseError,
+    ParseErrorKind, Part, PartAnswer, SolveError, SolveErrorKind,
+};
 
 pub struct Solution;
 
+impl Solution {
+    pub const DAY_NUMBER: u8 = 2;
+}
+
 impl DailySolution for Solution {
-    const DAY_NUMBER: u8 = 2;
+    fn day_number(&self) -> u8 {
+        Self::DAY_NUMBER
+    }
+
+    fn title(&self) -> &'static str {
+        "Dive!"
+    }
+
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error> {
+        let input_content = read_content(reader)?;
+
+        Ok(Box::new(Course(parse_commands(&input_content)?)))
+    }
+}
+
+/// A command of the planned course of the submarine.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Command {
+    Forward(isize),
+    Back(isize),
+    Down(isize),
+    Up(isize),
+    /// Stay in place for a step.
+    Hold,
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Forward(units) => write!(f, "forward {}", units),
+            Self::Back(units) => write!(f, "back {}", units),
+            Self::Down(units) => write!(f, "down {}", units),
+            Self::Up(units) => write!(f, "up {}", units),
+            Self::Hold => f.write_str("hold"),
+        }
+    }
+}
 
-    fn run_solution<P>(input_path: P) -> Result<(), Error>
+/// A command along with the number of the line it is read from.
+pub type NumberedCommand = (usize, Command);
+
+/// The position of the submarine.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Position {
+    pub horizontal: isize,
+    pub depth: isize,
+}
+
+impl Position {
+    /// The puzzle score of the position, its horizontal position multiplied by its depth, or None
+    /// if it overflows.
+    pub fn score(&self) -> Option<isize> {
+        self.horizontal.checked_mul(self.depth)
+    }
+}
+
+/// The failure of the command read from provided line.
+fn command_error(line_number: usize, command: Command, kind: SolveErrorKind) -> Error {
+    SolveError::new(
+        Solution::DAY_NUMBER,
+        Some(line_number),
+        command.to_string(),
+        kind,
+    )
+    .into()
+}
+
+/// A movement model of the submarine, running commands one at a time.
+pub trait Interpreter {
+    /// Run a command, failing without moving if the resulting state overflows.
+    fn execute(&mut self, command: Command) -> Result<(), SolveErrorKind>;
+
+    /// The position reached by the commands run so far.
+    fn position(&self) -> Position;
+
+    /// The current aim, always 0 for models without aim.
+    fn aim(&self) -> isize {
+        0
+    }
+
+    /// Run every command of a course, returning the reached position.
+    fn run(&mut self, commands: &[NumberedCommand]) -> Result<Position, Error> {
+        for &(line_number, command) in commands {
+            self.execute(command)
+                .map_err(|kind| command_error(line_number, command, kind))?;
+        }
+
+        Ok(self.position())
+    }
+
+    /// Run every command of a course, returning the state after each of them, preceded by the
+    /// initial state.
+    fn trace(&mut self, commands: &[NumberedCommand]) -> Result<Vec<Step>, Error> {
+        let mut steps = Vec::with_capacity(commands.len() + 1);
+        steps.push(Step::new(0, None, self));
+
+        for (index, &(line_number, command)) in commands.iter().enumerate() {
+            self.execute(command)
+                .map_err(|kind| command_error(line_number, command, kind))?;
+            steps.push(Step::new(index + 1, Some(command), self));
+        }
+
+        Ok(steps)
+    }
+}
+
+/// The interpreter of the movement model of provided puzzle part.
+pub fn interpreter(part: Part) -> Box<dyn Interpreter> {
+    match part {
+        Part::One => Box::new(DirectModel::default()),
+        Part::Two => Box::new(AimModel::default()),
+    }
+}
+
+/// The state of the submarine after a step of its course.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Step {
+    pub index: usize,
+    /// The command run by the step, or None for the initial state.
+    pub command: Option<Command>,
+    pub position: Position,
+    pub aim: isize,
+}
+
+impl Step {
+    fn new<I>(index: usize, command: Option<Command>, interpreter: &I) -> Self
     where
-        P: AsRef<Path>,
+        I: Interpreter + ?Sized,
     {
-        let input_content = fs::read_to_string(input_path)?;
+        Self {
+            index,
+            command,
+            position: interpreter.position(),
+            aim: interpreter.aim(),
+        }
+    }
+}
 
-        let part_1_result = part_1(&input_content);
-        println!("Final position score: {}", part_1_result);
+/// The available renderings of a trajectory.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TrajectoryFormat {
+    Csv,
+    Svg,
+}
 
-        let part_2_result = part_2(&input_content);
-        println!("Final aimed position score: {}", part_2_result);
+impl FromStr for TrajectoryFormat {
+    type Err = String;
 
-        Ok(())
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "svg" => Ok(Self::Svg),
+            _ => Err(format!(
+                "unknown trajectory format '{}', expected csv or svg",
+                s
+            )),
+        }
+    }
+}
+
+impl TrajectoryFormat {
+    /// Render provided trajectory with this format.
+    pub fn render(self, steps: &[Step]) -> String {
+        match self {
+            Self::Csv => trajectory_csv(steps),
+            Self::Svg => trajectory_svg(steps),
+        }
     }
 }
 
-fn part_1(input_content: &str) -> usize {
-    let mut vertical_pos = 0;
-    let mut horizontal_pos = 0;
+/// Render a trajectory as CSV, with a header row and one row per step.
+pub fn trajectory_csv(steps: &[Step]) -> String {
+    let mut csv = String::from("step,command,horizontal,depth,aim\n");
 
-    let operation_regex = RegexBuilder::new(r"^(?P<operator>forward|down|up) (?P<units>\d+)$")
-        .multi_line(true)
-        .build()
+    for step in steps {
+        let command = step.command.map(|command| command.to_string());
+        writeln!(
+            csv,
+            "{},{},{},{},{}",
+            step.index,
+            command.unwrap_or_default(),
+            step.position.horizontal,
+            step.position.depth,
+            step.aim
+        )
         .unwrap();
+    }
+
+    csv
+}
+
+/// Render a trajectory as an SVG image plotting the depth against the horizontal position, as a
+/// path going down as the depth increases.
+///
+/// As depths may grow much faster than horizontal positions, both axes are stretched to fill the
+/// image.
+pub fn trajectory_svg(steps: &[Step]) -> String {
+    const WIDTH: usize = 800;
+    const HEIGHT: usize = 400;
+
+    let horizontal = steps.iter().map(|step| step.position.horizontal);
+    let depth = steps.iter().map(|step| step.position.depth);
+    let (min_x, max_x) = (horizontal.clone().min(), horizontal.max());
+    let (min_y, max_y) = (depth.clone().min(), depth.max());
+    let (min_x, min_y) = (min_x.unwrap_or(0), min_y.unwrap_or(0));
+    let width = max_x.unwrap_or(0).abs_diff(min_x).max(1);
+    let height = max_y.unwrap_or(0).abs_diff(min_y).max(1);
+
+    let path: Vec<String> = steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| {
+            let instruction = if index == 0 { 'M' } else { 'L' };
+            format!(
+                "{} {} {}",
+                instruction, step.position.horizontal, step.position.depth
+            )
+        })
+        .collect();
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}" preserveAspectRatio="none">"#,
+        WIDTH, HEIGHT, min_x, min_y, width, height
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"  <path d="{}" fill="none" stroke="steelblue" stroke-width="2" vector-effect="non-scaling-stroke"/>"#,
+        path.join(" ")
+    )
+    .unwrap();
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+/// The model of the first part, where vertical commands change the depth directly.
+#[derive(Clone, Debug, Default)]
+pub struct DirectModel {
+    position: Position,
+}
+
+impl Interpreter for DirectModel {
+    fn execute(&mut self, command: Command) -> Result<(), SolveErrorKind> {
+        let Position { horizontal, depth } = self.position;
+        let (horizontal, depth) = match command {
+            Command::Forward(units) => (horizontal.checked_add(units), Some(depth)),
+            Command::Back(units) => (horizontal.checked_sub(units), Some(depth)),
+            Command::Down(units) => (Some(horizontal), depth.checked_add(units)),
+            Command::Up(units) => (Some(horizontal), depth.checked_sub(units)),
+            Command::Hold => return Ok(()),
+        };
 
-    for cap in operation_regex.captures_iter(input_content) {
-        let units = cap["units"].parse::<isize>().unwrap();
-        match &cap["operator"] {
-            "forward" => horizontal_pos += units,
-            "down" => vertical_pos += units,
-            "up" => vertical_pos -= units,
-            _ => panic!("unknown operator '{}'", &cap["operator"]),
+        match horizontal.zip(depth) {
+            Some((horizontal, depth)) => {
+                self.position = Position { horizontal, depth };
+                Ok(())
+            }
+            None => Err(SolveErrorKind::Overflow),
         }
     }
 
-    usize::try_from(vertical_pos * horizontal_pos).unwrap()
+    fn position(&self) -> Position {
+        self.position
+    }
 }
-fn part_2(input_content: &str) -> usize {
-    let mut aim = 0;
-    let mut vertical_pos = 0;
-    let mut horizontal_pos = 0;
 
-    let operation_regex = RegexBuilder::new(r"^(?P<operator>forward|down|up) (?P<units>\d+)$")
-        .multi_line(true)
-        .build()
-        .unwrap();
+/// The model of the second part, where vertical commands change the aim, and the depth changes
+/// by the aim for each unit moved horizontally.
+#[derive(Clone, Debug, Default)]
+pub struct AimModel {
+    position: Position,
+    aim: isize,
+}
 
-    for cap in operation_regex.captures_iter(input_content) {
-        let units = cap["units"].parse::<isize>().unwrap();
-        match &cap["operator"] {
-            "forward" => {
-                horizontal_pos += units;
-                vertical_pos += aim * units;
+impl Interpreter for AimModel {
+    fn execute(&mut self, command: Command) -> Result<(), SolveErrorKind> {
+        let Position { horizontal, depth } = self.position;
+        let dive = |units: isize| self.aim.checked_mul(units);
+        let moved = match command {
+            Command::Forward(units) => horizontal
+                .checked_add(units)
+                .zip(dive(units).and_then(|dive| depth.checked_add(dive))),
+            Command::Back(units) => horizontal
+                .checked_sub(units)
+                .zip(dive(units).and_then(|dive| depth.checked_sub(dive))),
+            Command::Down(units) => {
+                self.aim = self
+                    .aim
+                    .checked_add(units)
+                    .ok_or(SolveErrorKind::Overflow)?;
+                return Ok(());
             }
-            "down" => aim += units,
-            "up" => aim -= units,
-            _ => panic!("unknown operator '{}'", &cap["operator"]),
+            Command::Up(units) => {
+                self.aim = self
+                    .aim
+                    .checked_sub(units)
+                    .ok_or(SolveErrorKind::Overflow)?;
+                return Ok(());
+            }
+            Command::Hold => return Ok(()),
+        };
+
+        let (horizontal, depth) = moved.ok_or(SolveErrorKind::Overflow)?;
+        self.position = Position { horizontal, depth };
+        Ok(())
+    }
+
+    fn position(&self) -> Position {
+        self.position
+    }
+
+    fn aim(&self) -> isize {
+        self.aim
+    }
+}
+
+/// The planned course of the submarine, as a list of commands.
+struct Course(Vec<NumberedCommand>);
+
+impl DailyInput for Course {
+    fn solve(&self, part: Part) -> Result<PartAnswer, Error> {
+        Ok(match part {
+            Part::One => PartAnswer::new(part, "Final position score", part_1(&self.0)?),
+            Part::Two => PartAnswer::new(part, "Final aimed position score", part_2(&self.0)?),
+        })
+    }
+
+    fn record_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Read each non-empty line as a command, failing on unknown commands.
+pub fn parse_commands(input_content: &str) -> Result<Vec<NumberedCommand>, Error> {
+    input_content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| Ok((index + 1, parse_command(index + 1, line)?)))
+        .collect()
+}
+
+/// Read a command as `<operator> <units>`, or as `hold` which has no units.
+fn parse_command(line_number: usize, line: &str) -> Result<Command, Error> {
+    let invalid_format = |column: usize, text: &str| {
+        Error::from(ParseError::new(
+            Solution::DAY_NUMBER,
+            line_number,
+            column,
+            text,
+            ParseErrorKind::InvalidFormat,
+        ))
+    };
+    let fields: Vec<(usize, &str)> = split_fields(line, ' ').collect();
+    let (operator_column, operator) = fields[0];
+    let parse_units = || match fields.get(1) {
+        Some(&(column, units)) => {
+            parse_number::<isize>(Solution::DAY_NUMBER, line_number, column, units)
         }
+        None => Err(invalid_format(
+            operator_column + operator.len(),
+            line.trim(),
+        )),
+    };
+
+    let (command, field_count) = match operator {
+        "forward" => (Command::Forward(parse_units()?), 2),
+        "back" => (Command::Back(parse_units()?), 2),
+        "down" => (Command::Down(parse_units()?), 2),
+        "up" => (Command::Up(parse_units()?), 2),
+        "hold" => (Command::Hold, 1),
+        _ => return Err(invalid_format(operator_column, operator)),
+    };
+    match fields.get(field_count) {
+        Some(&(column, text)) => Err(invalid_format(column, text)),
+        None => Ok(command),
     }
+}
+
+/// The score of a position as an answer, which is only signed if it is negative.
+fn score_answer(position: Position) -> Result<Answer, Error> {
+    let score = position.score().ok_or_else(|| {
+        SolveError::new(
+            Solution::DAY_NUMBER,
+            None,
+            format!("score of ({}, {})", position.horizontal, position.depth),
+            SolveErrorKind::Overflow,
+        )
+    })?;
+
+    Ok(usize::try_from(score).map_or(Answer::Signed(score), Answer::Number))
+}
 
-    usize::try_from(vertical_pos * horizontal_pos).unwrap()
+fn part_1(commands: &[NumberedCommand]) -> Result<Answer, Error> {
+    score_answer(DirectModel::default().run(commands)?)
+}
+
+fn part_2(commands: &[NumberedCommand]) -> Result<Answer, Error> {
+    score_answer(AimModel::default().run(commands)?)
 }
 
 #[cfg(test)]
 mod test_day {
-    use super::{part_1, part_2};
+    use super::{
+        interpreter, parse_commands, AimModel, Command, DirectModel, Interpreter, Position,
+        Solution, TrajectoryFormat,
+    };
+    use crate::{
+        expect_parse_error, expect_solve_error, DailySolution, ParseErrorKind, Part, SolveError,
+        SolveErrorKind,
+    };
 
     /// Sample lines from guidelines
     const TEST_LINES: &str = "forward 5
@@ -88,13 +446,160 @@ up 3
 down 8
 forward 2";
 
+    crate::example_tests!(super::Solution, {
+        test_guidelines: TEST_LINES => [150, 900],
+        test_extended_commands: "down 2\nforward 3\nhold\n\nback 1\nup 5" => [crate::Answer::Signed(-6), 8],
+    });
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            parse_commands("forward 5\n  up -3  \nhold\n").unwrap(),
+            vec![
+                (1, Command::Forward(5)),
+                (2, Command::Up(-3)),
+                (3, Command::Hold)
+            ]
+        );
+        assert_eq!(Command::Back(-2).to_string(), "back -2");
+
+        for (content, position, text) in [
+            ("forward 5\nsideways 2", (2, 1), "sideways"),
+            ("forward", (1, 8), "forward"),
+            ("down 2 3", (1, 8), "3"),
+            ("hold 1", (1, 6), "1"),
+        ] {
+            let err = expect_parse_error(parse_commands(content));
+            assert_eq!((err.line, err.column), position);
+            assert_eq!(err.text, text);
+            assert_eq!(err.kind, ParseErrorKind::InvalidFormat);
+        }
+    }
+
+    #[test]
+    fn test_interpreters() {
+        let commands = parse_commands(TEST_LINES).unwrap();
+
+        let mut direct_model = DirectModel::default();
+        assert_eq!(
+            direct_model.run(&commands).unwrap(),
+            Position {
+                horizontal: 15,
+                depth: 10
+            }
+        );
+
+        let mut aim_model = AimModel::default();
+        assert_eq!(aim_model.run(&commands).unwrap().score(), Some(900));
+        assert_eq!(aim_model.aim(), 10);
+        aim_model.execute(Command::Back(5)).unwrap();
+        assert_eq!(
+            aim_model.position(),
+            Position {
+                horizontal: 10,
+                depth: 10
+            }
+        );
+
+        let interpreter: &mut dyn Interpreter = &mut DirectModel::default();
+        interpreter.execute(Command::Down(4)).unwrap();
+        assert_eq!(interpreter.position().depth, 4);
+    }
+
     #[test]
-    fn test_part_1() {
-        assert_eq!(part_1(TEST_LINES), 150);
+    fn test_trace() {
+        let commands = parse_commands(TEST_LINES).unwrap();
+
+        let steps = interpreter(Part::Two).trace(&commands).unwrap();
+        assert_eq!(steps.len(), 7);
+        assert_eq!(
+            (steps[0].command, steps[0].position),
+            (None, Position::default())
+        );
+        assert_eq!(steps[4].command, Some(Command::Up(3)));
+        assert_eq!(
+            (
+                steps[4].position.horizontal,
+                steps[4].position.depth,
+                steps[4].aim
+            ),
+            (13, 40, 2)
+        );
+        assert_eq!(steps[6].position.score(), Some(900));
+
+        let steps = interpreter(Part::One).trace(&commands).unwrap();
+        assert!(steps.iter().all(|step| step.aim == 0));
+        assert_eq!(steps[6].position.score(), Some(150));
     }
 
     #[test]
-    fn test_part_2() {
-        assert_eq!(part_2(TEST_LINES), 900);
+    fn test_export_trajectory() {
+        let commands = parse_commands("forward 5\ndown 5\nforward 8").unwrap();
+        let steps = interpreter(Part::Two).trace(&commands).unwrap();
+
+        assert_eq!(
+            TrajectoryFormat::Csv.render(&steps),
+            "step,command,horizontal,depth,aim
+0,,0,0,0
+1,forward 5,5,0,0
+2,down 5,5,0,5
+3,forward 8,13,40,5
+"
+        );
+
+        let svg = TrajectoryFormat::Svg.render(&steps);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains(r#"viewBox="0 0 13 40""#));
+        assert!(svg.contains(r#"d="M 0 0 L 5 0 L 5 0 L 13 40""#));
+        assert!(svg.ends_with("</svg>\n"));
+
+        assert!(TrajectoryFormat::Svg
+            .render(&[])
+            .contains(r#"viewBox="0 0 1 1""#));
+
+        let commands = parse_commands(
+            "back 9223372036854775807\nforward 9223372036854775807\nforward 9223372036854775807",
+        )
+        .unwrap();
+        let steps = interpreter(Part::One).trace(&commands).unwrap();
+        assert!(TrajectoryFormat::Svg
+            .render(&steps)
+            .contains(r#"viewBox="-9223372036854775807 0 18446744073709551614 1""#));
+        assert_eq!("SVG".parse::<TrajectoryFormat>(), Ok(TrajectoryFormat::Svg));
+    }
+
+    #[test]
+    fn test_units_overflow() {
+        let err = expect_parse_error(parse_commands("forward 5\ndown 99999999999999999999"));
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.kind, ParseErrorKind::InvalidNumber);
+    }
+
+    #[test]
+    fn test_position_overflow() {
+        let commands = parse_commands("forward 9223372036854775807\n\nforward 1").unwrap();
+        let mut direct_model = DirectModel::default();
+        let err = expect_solve_error(direct_model.run(&commands));
+        assert_eq!(
+            err,
+            SolveError::new(2, Some(3), "forward 1", SolveErrorKind::Overflow)
+        );
+        assert_eq!(
+            err.to_string(),
+            "day 2, line 3: arithmetic overflow 'forward 1'"
+        );
+        assert_eq!(direct_model.position().horizontal, isize::MAX);
+        assert!(interpreter(Part::Two).trace(&commands).is_err());
+
+        let commands = parse_commands("down 4000000000\nforward 4000000000").unwrap();
+        assert!(AimModel::default().run(&commands).is_err());
+        let err = expect_solve_error(
+            Solution.run_str("down 4000000000\nforward 4000000000", &[Part::One]),
+        );
+        assert_eq!((err.line, err.kind), (None, SolveErrorKind::Overflow));
+        assert_eq!(err.text, "score of (4000000000, 4000000000)");
+        assert!(Solution
+            .run_str("up 1\nforward 5", &[Part::One, Part::Two])
+            .is_ok());
     }
 }
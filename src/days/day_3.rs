@@ -1,35 +1,51 @@
 //! This is the day 3 module.
 //! The instructions are available here: https://adventofcode.com/2021/day/3
 
-use std::fs;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::BufRead;
 
-use crate::{DailySolution, Error};
+use crate::{
+    DailyInput, DailySolution, Error, ParseError, ParseErrorKind, Part, PartAnswer, SolveError,
+    SolveErrorKind,
+};
 
 pub struct Solution;
 
+impl Solution {
+    pub const DAY_NUMBER: u8 = 3;
+}
+
 impl DailySolution for Solution {
-    const DAY_NUMBER: u8 = 3;
+    fn day_number(&self) -> u8 {
+        Self::DAY_NUMBER
+    }
 
-    fn run_solution<P>(input_path: P) -> Result<(), Error>
-    where
-        P: AsRef<Path>,
-    {
-        let input_lines: Vec<String> = BufReader::new(fs::File::open(input_path)?)
-            .lines()
-            .map(|l| l.unwrap())
-            .collect();
+    fn title(&self) -> &'static str {
+        "Binary Diagnostic"
+    }
 
-        let position_counters = count_bit_positions(&input_lines);
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error> {
+        let input_lines = parse_report(reader)?;
+
+        Ok(Box::new(DiagnosticReport(input_lines)))
+    }
+}
 
-        let part_1_result = part_1(&position_counters);
-        println!("Power consumption is: {}", part_1_result);
+/// The lines of the diagnostic report.
+struct DiagnosticReport(Vec<String>);
 
-        let part_2_result = part_2(&input_lines);
-        println!("Life support rating is: {}", part_2_result);
+impl DailyInput for DiagnosticReport {
+    fn solve(&self, part: Part) -> Result<PartAnswer, Error> {
+        Ok(match part {
+            Part::One => {
+                let position_counters = count_bit_positions(&self.0);
+                PartAnswer::new(part, "Power consumption", part_1(&position_counters)?)
+            }
+            Part::Two => PartAnswer::new(part, "Life support rating", part_2(&self.0)?),
+        })
+    }
 
-        Ok(())
+    fn record_count(&self) -> usize {
+        self.0.len()
     }
 }
 
@@ -63,10 +79,10 @@ impl CharNode {
             Some(new_char) => new_char,
         };
 
-        let mut next_node = match new_char {
+        let next_node = match new_char {
             '0' => &mut self.zero_side,
             '1' => &mut self.one_side,
-            _ => panic!("unknown binary char '{}'", new_char),
+            _ => unreachable!("unknown binary char '{}'", new_char),
         }
         .get_or_insert_with(|| {
             Box::new(Self {
@@ -82,6 +98,43 @@ impl CharNode {
     }
 }
 
+/// Read the diagnostic report, ensuring each line is only made of binary chars and fits into a
+/// `usize`.
+fn parse_report<R>(reader: R) -> Result<Vec<String>, Error>
+where
+    R: BufRead,
+{
+    let mut lines = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?.trim().to_string();
+
+        if let Some((column, char)) = line.char_indices().find(|(_, c)| *c != '0' && *c != '1') {
+            return Err(ParseError::new(
+                Solution::DAY_NUMBER,
+                index + 1,
+                column + 1,
+                char,
+                ParseErrorKind::UnexpectedChar,
+            )
+            .into());
+        }
+        if line.len() > usize::BITS as usize {
+            return Err(ParseError::new(
+                Solution::DAY_NUMBER,
+                index + 1,
+                usize::BITS as usize + 1,
+                line,
+                ParseErrorKind::OutOfRange,
+            )
+            .into());
+        }
+        lines.push(line);
+    }
+
+    Ok(lines)
+}
+
 fn count_bit_positions(lines: &[String]) -> Vec<(usize, usize)> {
     // An array with counters for each index as (0 count, 1 count)
     let mut position_counters = Vec::new();
@@ -94,7 +147,7 @@ fn count_bit_positions(lines: &[String]) -> Vec<(usize, usize)> {
             match char {
                 '0' => position_counters[index].0 += 1,
                 '1' => position_counters[index].1 += 1,
-                _ => panic!("unknown char '{}'", char),
+                _ => unreachable!("unknown char '{}'", char),
             }
         }
     }
@@ -108,12 +161,6 @@ fn evaluate_rating<F>(current_node: &CharNode, mut use_side_one: F) -> (usize, u
 where
     F: FnMut(usize, usize) -> bool,
 {
-    let current_bit = match current_node.label {
-        '0' => 0b0,
-        '1' => 0b1,
-        '#' => 0b0,
-        _ => panic!("unknown bit char '{}'", current_node.label),
-    };
     let (sub_rating, depth) = match (&current_node.zero_side, &current_node.one_side) {
         (None, None) => (0b0, 0),
         (Some(zero_node), Some(one_node)) => {
@@ -127,12 +174,31 @@ where
         (None, Some(one_node)) => evaluate_rating(one_node, use_side_one),
     };
 
-    ((current_bit << depth) | sub_rating, depth + 1)
+    let rating = match current_node.label {
+        '1' => (0b1 << depth) | sub_rating,
+        '0' | CharNode::ROOT_CHAR => sub_rating,
+        _ => panic!("unknown bit char '{}'", current_node.label),
+    };
+
+    (rating, depth + 1)
 }
 
-fn part_1(position_counters: &[(usize, usize)]) -> usize {
-    let mut gamma = 0;
-    let mut epsilon = 0;
+/// The product of two rates, failing if it overflows.
+fn multiply_rates(first: usize, second: usize) -> Result<usize, Error> {
+    first.checked_mul(second).ok_or_else(|| {
+        SolveError::new(
+            Solution::DAY_NUMBER,
+            None,
+            format!("{} * {}", first, second),
+            SolveErrorKind::Overflow,
+        )
+        .into()
+    })
+}
+
+fn part_1(position_counters: &[(usize, usize)]) -> Result<usize, Error> {
+    let mut gamma: usize = 0;
+    let mut epsilon: usize = 0;
 
     for (zero_counter, one_counter) in position_counters {
         // left shift both gamma and epsilon to meet bits size
@@ -148,10 +214,10 @@ fn part_1(position_counters: &[(usize, usize)]) -> usize {
         }
     }
 
-    usize::try_from(gamma * epsilon).unwrap()
+    multiply_rates(gamma, epsilon)
 }
 
-fn part_2(lines: &[String]) -> usize {
+fn part_2(lines: &[String]) -> Result<usize, Error> {
     let mut tree_root = CharNode::root();
 
     for line in lines {
@@ -163,12 +229,15 @@ fn part_2(lines: &[String]) -> usize {
     let (co2_scrubber_rating, _) =
         evaluate_rating(&tree_root, |zero_side, one_side| one_side < zero_side);
 
-    oxygen_generator_rating * co2_scrubber_rating
+    multiply_rates(oxygen_generator_rating, co2_scrubber_rating)
 }
 
 #[cfg(test)]
 mod test_day {
-    use super::{count_bit_positions, part_1, part_2};
+    use super::{parse_report, Solution};
+    use crate::{
+        expect_parse_error, expect_solve_error, DailySolution, ParseErrorKind, Part, SolveErrorKind,
+    };
 
     /// Sample lines from guidelines
     const TEST_LINES: &str = "00100
@@ -184,18 +253,34 @@ mod test_day {
 00010
 01010";
 
-    #[test]
-    fn test_part_1() {
-        let input_lines: Vec<String> = TEST_LINES.lines().map(String::from).collect();
-        let position_counter = count_bit_positions(&input_lines);
+    crate::example_tests!(super::Solution, {
+        test_guidelines: TEST_LINES => [198, 230],
+    });
 
-        assert_eq!(part_1(&position_counter), 198);
+    #[test]
+    fn test_unknown_binary_char() {
+        let err = expect_parse_error(parse_report("00100\n11210".as_bytes()));
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "2");
+        assert_eq!(err.kind, ParseErrorKind::UnexpectedChar);
     }
 
     #[test]
-    fn test_part_2() {
-        let input_lines: Vec<String> = TEST_LINES.lines().map(String::from).collect();
-
-        assert_eq!(part_2(&input_lines), 230);
+    fn test_wide_lines() {
+        let line = format!("{}{}", "1".repeat(32), "0".repeat(32));
+        for part in Part::ALL {
+            let err = expect_solve_error(Solution.run_str(&line, &[part]));
+            assert_eq!(err.kind, SolveErrorKind::Overflow);
+        }
+        assert!(Solution
+            .run_str(
+                &format!("{}\n{}", "0".repeat(64), "1".repeat(64)),
+                &Part::ALL
+            )
+            .is_ok());
+
+        let err = expect_parse_error(parse_report(format!("0\n{}", "1".repeat(70)).as_bytes()));
+        assert_eq!((err.line, err.column), (2, 65));
+        assert_eq!(err.kind, ParseErrorKind::OutOfRange);
     }
 }
@@ -1,36 +1,82 @@
 //! This is the day 4 module.
 //! The instructions are available here: https://adventofcode.com/2021/day/4
 
-use std::fs;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::BufRead;
 
-use crate::{DailySolution, Error};
+use crate::{
+    parse_number, split_fields, DailyInput, DailySolution, Error, ParseError, ParseErrorKind, Part,
+    PartAnswer, SolveError, SolveErrorKind,
+};
 
 pub struct Solution;
 
+impl Solution {
+    pub const DAY_NUMBER: u8 = 4;
+}
+
 impl DailySolution for Solution {
-    const DAY_NUMBER: u8 = 4;
-
-    fn run_solution<P>(input_path: P) -> Result<(), Error>
-    where
-        P: AsRef<Path>,
-    {
-        let input_lines: Vec<String> = normalize_lines(
-            BufReader::new(fs::File::open(input_path)?)
+    fn day_number(&self) -> u8 {
+        Self::DAY_NUMBER
+    }
+
+    fn title(&self) -> &'static str {
+        "Giant Squid"
+    }
+
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error> {
+        let input_lines = normalize_lines(
+            reader
                 .lines()
-                .map(|l| l.unwrap()),
+                .collect::<Result<Vec<String>, _>>()?
+                .into_iter(),
         );
-        let pick_list = get_pick_list(&input_lines);
-        let grids = generate_grids(&input_lines[1..]);
+        let pick_list = get_pick_list(&input_lines)?;
+        let grids = generate_grids(&input_lines[1..])?;
+
+        Ok(Box::new(Bingo {
+            pick_line: input_lines[0].clone(),
+            pick_list,
+            grids,
+        }))
+    }
+}
 
-        let part_1_result = part_1(&pick_list, grids.clone());
-        println!("First winner grid score: {}", part_1_result);
+/// The bingo subsystem, with picked numbers and grids not marked yet.
+struct Bingo {
+    /// The line of the picked numbers, which locates the failure when no grid wins.
+    pick_line: NumberedLine,
+    pick_list: Vec<usize>,
+    grids: Vec<BingoGrid>,
+}
 
-        let part_2_result = part_2(&pick_list, grids);
-        println!("Last winner grid score: {}", part_2_result);
+impl DailyInput for Bingo {
+    fn solve(&self, part: Part) -> Result<PartAnswer, Error> {
+        let (line_number, line) = &self.pick_line;
+        let no_winner = || {
+            Error::from(SolveError::new(
+                Solution::DAY_NUMBER,
+                Some(*line_number),
+                line.trim(),
+                SolveErrorKind::NoSolution,
+            ))
+        };
+
+        Ok(match part {
+            Part::One => PartAnswer::new(
+                part,
+                "First winner grid score",
+                part_1(&self.pick_list, self.grids.clone()).ok_or_else(no_winner)?,
+            ),
+            Part::Two => PartAnswer::new(
+                part,
+                "Last winner grid score",
+                part_2(&self.pick_list, self.grids.clone()).ok_or_else(no_winner)?,
+            ),
+        })
+    }
 
-        Ok(())
+    fn record_count(&self) -> usize {
+        self.grids.len()
     }
 }
 
@@ -41,25 +87,27 @@ struct BingoGrid {
 }
 
 impl BingoGrid {
-    fn from_lines(lines: &[String]) -> Self {
+    fn from_lines(lines: &[NumberedLine]) -> Result<Self, Error> {
         if lines.len() != 5 {
-            panic!("invalid chunk size: {:?}", lines);
+            let (line_number, line) = &lines[0];
+            return Err(invalid_size(*line_number, line));
         }
 
         let mut new_grid = [[(0usize, false); 5]; 5];
 
-        for (row_index, line) in lines.iter().enumerate() {
-            for (column_index, number) in line
-                .split(' ')
-                .filter(|x| !x.is_empty())
-                .map(|x| x.parse::<usize>().unwrap())
-                .enumerate()
-            {
+        for (row_index, (line_number, line)) in lines.iter().enumerate() {
+            let fields: Vec<(usize, &str)> = split_fields(line, ' ').collect();
+            if fields.len() != 5 {
+                return Err(invalid_size(*line_number, line));
+            }
+
+            for (column_index, (column, field)) in fields.into_iter().enumerate() {
+                let number = parse_number(Solution::DAY_NUMBER, *line_number, column, field)?;
                 new_grid[row_index][column_index] = (number, false);
             }
         }
 
-        Self { grid: new_grid }
+        Ok(Self { grid: new_grid })
     }
 
     fn mark_number(&mut self, number: usize) {
@@ -96,47 +144,73 @@ impl BingoGrid {
     }
 }
 
-fn normalize_lines<I>(raw_lines: I) -> Vec<String>
+/// A non-empty input line with its line number.
+type NumberedLine = (usize, String);
+
+fn invalid_size(line_number: usize, line: &str) -> Error {
+    ParseError::new(
+        Solution::DAY_NUMBER,
+        line_number,
+        1,
+        line.trim(),
+        ParseErrorKind::InvalidSize,
+    )
+    .into()
+}
+
+fn normalize_lines<I>(raw_lines: I) -> Vec<NumberedLine>
 where
     I: Iterator<Item = String>,
 {
     raw_lines
-        .map(|l| l.trim().to_string())
-        .filter(|l| !l.is_empty())
+        .enumerate()
+        .map(|(index, l)| (index + 1, l))
+        .filter(|(_, l)| !l.trim().is_empty())
         .collect()
 }
 
-fn get_pick_list(lines: &[String]) -> Vec<usize> {
-    lines[0]
-        .split(',')
-        .map(|x| x.parse::<usize>().unwrap())
+fn get_pick_list(lines: &[NumberedLine]) -> Result<Vec<usize>, Error> {
+    let (line_number, line) = lines.first().ok_or_else(|| {
+        Error::from(ParseError::new(
+            Solution::DAY_NUMBER,
+            1,
+            1,
+            "",
+            ParseErrorKind::EmptyInput,
+        ))
+    })?;
+
+    split_fields(line, ',')
+        .map(|(column, field)| parse_number(Solution::DAY_NUMBER, *line_number, column, field))
         .collect()
 }
 
-fn generate_grids(lines: &[String]) -> Vec<BingoGrid> {
+fn generate_grids(lines: &[NumberedLine]) -> Result<Vec<BingoGrid>, Error> {
     let mut grids = Vec::new();
     for chunk in lines.chunks(5) {
-        let new_grid = BingoGrid::from_lines(chunk);
+        let new_grid = BingoGrid::from_lines(chunk)?;
         grids.push(new_grid);
     }
 
-    grids
+    Ok(grids)
 }
 
-fn part_1(pick_list: &[usize], mut grids: Vec<BingoGrid>) -> usize {
+/// The score of the first winner grid, or None if no grid wins.
+fn part_1(pick_list: &[usize], mut grids: Vec<BingoGrid>) -> Option<usize> {
     for &number in pick_list {
         for grid in grids.iter_mut() {
             grid.mark_number(number);
             if grid.is_winner() {
-                return number * grid.get_unmarked_sum();
+                return Some(number * grid.get_unmarked_sum());
             }
         }
     }
 
-    panic!("no winner grid");
+    None
 }
 
-fn part_2(pick_list: &[usize], mut grids: Vec<BingoGrid>) -> usize {
+/// The score of the last winner grid, or None if no grid wins.
+fn part_2(pick_list: &[usize], mut grids: Vec<BingoGrid>) -> Option<usize> {
     let mut winner_scores = Vec::new();
     let mut winner_indexes = Vec::new();
 
@@ -155,12 +229,16 @@ fn part_2(pick_list: &[usize], mut grids: Vec<BingoGrid>) -> usize {
         }
     }
 
-    winner_scores.pop().expect("no winner found")
+    winner_scores.pop()
 }
 
 #[cfg(test)]
 mod test_day {
-    use super::{generate_grids, get_pick_list, normalize_lines, part_1, part_2, BingoGrid};
+    use super::{generate_grids, get_pick_list, normalize_lines, BingoGrid, Solution};
+    use crate::{
+        expect_parse_error, expect_solve_error, DailySolution, ParseErrorKind, Part, SolveError,
+        SolveErrorKind,
+    };
 
     /// Sample lines from guidelines
     const TEST_LINES: &str =
@@ -186,9 +264,9 @@ mod test_day {
 
     #[test]
     fn test_pick_list() {
-        let input_lines: Vec<String> = normalize_lines(TEST_LINES.lines().map(String::from));
+        let input_lines = normalize_lines(TEST_LINES.lines().map(String::from));
         assert_eq!(
-            get_pick_list(&input_lines),
+            get_pick_list(&input_lines).unwrap(),
             vec![
                 7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24, 10, 16, 13, 6, 15, 25, 12, 22, 18, 20, 8,
                 19, 3, 26, 1
@@ -199,7 +277,7 @@ mod test_day {
     #[test]
     fn test_grid_sum() {
         let marked_numbers: Vec<usize> = vec![14, 21, 17, 4, 9, 23, 11, 5, 2, 0, 7, 24];
-        let input_grid: Vec<String> = normalize_lines(
+        let input_grid = normalize_lines(
             "14 21 17 24  4
 10 16 15  9 19
 18  8 23 26 20
@@ -208,28 +286,47 @@ mod test_day {
                 .lines()
                 .map(String::from),
         );
-        let mut grid = BingoGrid::from_lines(&input_grid);
+        let mut grid = BingoGrid::from_lines(&input_grid).unwrap();
 
         marked_numbers.iter().for_each(|&x| grid.mark_number(x));
         assert!(grid.is_winner());
         assert_eq!(grid.get_unmarked_sum(), 188);
     }
 
-    #[test]
-    fn test_part_1() {
-        let input_lines: Vec<String> = normalize_lines(TEST_LINES.lines().map(String::from));
-        let pick_list = get_pick_list(&input_lines);
-        let grids = generate_grids(&input_lines[1..]);
+    crate::example_tests!(super::Solution, {
+        test_guidelines: TEST_LINES => [4512, 1924],
+    });
 
-        assert_eq!(part_1(&pick_list, grids), 4512);
+    #[test]
+    fn test_no_winner() {
+        let input = "\n1,2\n\n22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19";
+        let bingo = Solution.parse_input(&mut input.as_bytes()).unwrap();
+
+        for part in Part::ALL {
+            let err = expect_solve_error(bingo.solve(part));
+            assert_eq!(
+                err,
+                SolveError::new(4, Some(2), "1,2", SolveErrorKind::NoSolution)
+            );
+        }
     }
 
     #[test]
-    fn test_part_2() {
-        let input_lines: Vec<String> = normalize_lines(TEST_LINES.lines().map(String::from));
-        let pick_list = get_pick_list(&input_lines);
-        let grids = generate_grids(&input_lines[1..]);
-
-        assert_eq!(part_2(&pick_list, grids), 1924);
+    fn test_invalid_grids() {
+        let input_lines = normalize_lines(TEST_LINES.lines().map(String::from));
+        let err = expect_parse_error(generate_grids(&input_lines[1..14]));
+        assert_eq!(err.line, 15);
+        assert_eq!(err.kind, ParseErrorKind::InvalidSize);
+
+        let input_lines = normalize_lines(
+            TEST_LINES
+                .replace("23  4", "23 x4")
+                .lines()
+                .map(String::from),
+        );
+        let err = expect_parse_error(generate_grids(&input_lines[1..]));
+        assert_eq!((err.line, err.column), (4, 10));
+        assert_eq!(err.text, "x4");
+        assert_eq!(err.kind, ParseErrorKind::InvalidNumber);
     }
 }
@@ -2,32 +2,54 @@
 //! The instructions are available here: https://adventofcode.com/2021/day/5
 
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+use std::io::BufRead;
 
-use regex::RegexBuilder;
+use regex::Regex;
 
-use crate::{DailySolution, Error};
+use crate::{
+    parse_number, read_content, DailyInput, DailySolution, Error, ParseError, ParseErrorKind, Part,
+    PartAnswer,
+};
 
 pub struct Solution;
 
+impl Solution {
+    pub const DAY_NUMBER: u8 = 5;
+}
+
 impl DailySolution for Solution {
-    const DAY_NUMBER: u8 = 5;
+    fn day_number(&self) -> u8 {
+        Self::DAY_NUMBER
+    }
 
-    fn run_solution<P>(input_path: P) -> Result<(), Error>
-    where
-        P: AsRef<Path>,
-    {
-        let input_content = fs::read_to_string(input_path)?;
-        let segments = Segment::from_lines(&input_content);
+    fn title(&self) -> &'static str {
+        "Hydrothermal Venture"
+    }
+
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error> {
+        let input_content = read_content(reader)?;
 
-        let part_1_result = part_1(&segments);
-        println!("Total overlap points of straight lines: {}", part_1_result);
+        Ok(Box::new(VentLines(Segment::from_lines(&input_content)?)))
+    }
+}
 
-        let part_2_result = part_2(&segments);
-        println!("Total intersections: {}", part_2_result);
+/// The hydrothermal vent lines nearby.
+struct VentLines(Vec<Segment>);
+
+impl DailyInput for VentLines {
+    fn solve(&self, part: Part) -> Result<PartAnswer, Error> {
+        Ok(match part {
+            Part::One => PartAnswer::new(
+                part,
+                "Total overlap points of straight lines",
+                part_1(&self.0),
+            ),
+            Part::Two => PartAnswer::new(part, "Total intersections", part_2(&self.0)),
+        })
+    }
 
-        Ok(())
+    fn record_count(&self) -> usize {
+        self.0.len()
     }
 }
 
@@ -67,7 +89,7 @@ impl Point {
 
             x_range
                 .into_iter()
-                .zip(y_range.into_iter())
+                .zip(y_range)
                 .map(|(x, y)| Point { x, y })
                 .collect()
         }
@@ -78,24 +100,49 @@ impl Point {
 struct Segment(Point, Point);
 
 impl Segment {
-    fn from_lines(input_content: &str) -> Vec<Segment> {
+    fn from_lines(input_content: &str) -> Result<Vec<Segment>, Error> {
         let line_parser =
-            RegexBuilder::new(r"^(?P<x1>\d+),(?P<y1>\d+) -> (?P<x2>\d+),(?P<y2>\d+)$")
-                .multi_line(true)
-                .build()
-                .unwrap();
-
-        line_parser
-            .captures_iter(input_content)
-            .map(|cap| {
-                let x1 = cap["x1"].parse().unwrap();
-                let y1 = cap["y1"].parse().unwrap();
-                let x2 = cap["x2"].parse().unwrap();
-                let y2 = cap["y2"].parse().unwrap();
-
-                Segment(Point { x: x1, y: y1 }, Point { x: x2, y: y2 })
-            })
-            .collect()
+            Regex::new(r"^(?P<x1>\d+),(?P<y1>\d+) -> (?P<x2>\d+),(?P<y2>\d+)$").unwrap();
+        let mut segments = Vec::new();
+
+        for (index, line) in input_content.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+
+            let cap = line_parser.captures(line).ok_or_else(|| {
+                Error::from(ParseError::new(
+                    Solution::DAY_NUMBER,
+                    index + 1,
+                    1,
+                    line,
+                    ParseErrorKind::InvalidFormat,
+                ))
+            })?;
+            let parse_coordinate = |name: &str| -> Result<usize, Error> {
+                let coordinate = cap.name(name).unwrap();
+                parse_number(
+                    Solution::DAY_NUMBER,
+                    index + 1,
+                    coordinate.start() + 1,
+                    coordinate.as_str(),
+                )
+            };
+
+            segments.push(Segment(
+                Point {
+                    x: parse_coordinate("x1")?,
+                    y: parse_coordinate("y1")?,
+                },
+                Point {
+                    x: parse_coordinate("x2")?,
+                    y: parse_coordinate("y2")?,
+                },
+            ));
+        }
+
+        Ok(segments)
     }
 
     fn get_range(&self) -> Vec<Point> {
@@ -142,7 +189,8 @@ fn part_2(segments: &[Segment]) -> usize {
 mod test_day {
     use std::collections::HashSet;
 
-    use super::{count_intersections, count_points, part_1, part_2, Point, Segment};
+    use super::{count_intersections, count_points, Point, Segment};
+    use crate::{expect_parse_error, ParseErrorKind};
 
     /// Sample lines from guidelines
     const TEST_LINES: &str = "0,9 -> 5,9
@@ -158,55 +206,57 @@ mod test_day {
 
     #[test]
     fn test_diagonal_collinear() {
-        let segments = Segment::from_lines("6,0 -> 0,6\n6,0 -> 3,3");
+        let segments = Segment::from_lines("6,0 -> 0,6\n6,0 -> 3,3").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 4);
 
-        let segments = Segment::from_lines("0,0 -> 6,6\n0,0 -> 3,3");
+        let segments = Segment::from_lines("0,0 -> 6,6\n0,0 -> 3,3").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 4);
     }
 
     #[test]
     fn test_collinear() {
-        let segments = Segment::from_lines("0,9 -> 5,9\n0,9 -> 2,9");
+        let segments = Segment::from_lines("0,9 -> 5,9\n0,9 -> 2,9").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 3);
 
-        let segments = Segment::from_lines("0,9 -> 2,9\n2,9 -> 5,9");
+        let segments = Segment::from_lines("0,9 -> 2,9\n2,9 -> 5,9").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 1);
 
-        let segments = Segment::from_lines("9,0 -> 9,5\n9,0 -> 9,2");
+        let segments = Segment::from_lines("9,0 -> 9,5\n9,0 -> 9,2").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 3);
 
-        let segments = Segment::from_lines("9,4 -> 3,4\n3,4 -> 1,4\n2,2 -> 2,1\n7,0 -> 7,4");
+        let segments =
+            Segment::from_lines("9,4 -> 3,4\n3,4 -> 1,4\n2,2 -> 2,1\n7,0 -> 7,4").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 2);
     }
 
     #[test]
     fn test_no_intersection() {
-        let segments = Segment::from_lines("0,8 -> 3,8\n0,9 -> 3,9");
+        let segments = Segment::from_lines("0,8 -> 3,8\n0,9 -> 3,9").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 0);
 
-        let segments = Segment::from_lines("0,5 -> 3,5\n4,5 -> 8,5");
+        let segments = Segment::from_lines("0,5 -> 3,5\n4,5 -> 8,5").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 0);
 
-        let segments = Segment::from_lines("6,0 -> 4,2\n3,3 -> 2,4");
+        let segments = Segment::from_lines("6,0 -> 4,2\n3,3 -> 2,4").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 0);
 
-        let segments = Segment::from_lines("0,0 -> 2,2\n3,3 -> 4,4");
+        let segments = Segment::from_lines("0,0 -> 2,2\n3,3 -> 4,4").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 0);
     }
 
     #[test]
     fn test_diagonal_cross() {
-        let segments = Segment::from_lines("0,0 -> 6,6\n0,6 -> 6,0");
+        let segments = Segment::from_lines("0,0 -> 6,6\n0,6 -> 6,0").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 1);
 
-        let segments = Segment::from_lines("0,0 -> 6,6\n3,6 -> 3,0");
+        let segments = Segment::from_lines("0,0 -> 6,6\n3,6 -> 3,0").unwrap();
         assert_eq!(count_intersections(count_points(&segments)), 1);
     }
 
     #[test]
     fn test_known_intersections_straight() {
         let segments: Vec<Segment> = Segment::from_lines(TEST_LINES)
+            .unwrap()
             .into_iter()
             .filter(|seg| seg.0.x == seg.1.x || seg.0.y == seg.1.y)
             .collect();
@@ -225,7 +275,7 @@ mod test_day {
 
     #[test]
     fn test_known_intersections() {
-        let segments: Vec<Segment> = Segment::from_lines(TEST_LINES);
+        let segments: Vec<Segment> = Segment::from_lines(TEST_LINES).unwrap();
         let known_intersections = [
             (0, 9),
             (1, 9),
@@ -251,15 +301,15 @@ mod test_day {
         assert_eq!(intersections.difference(&known_intersections).count(), 0);
     }
 
-    #[test]
-    fn test_part_1() {
-        let segments = Segment::from_lines(TEST_LINES);
-        assert_eq!(part_1(&segments), 5);
-    }
+    crate::example_tests!(super::Solution, {
+        test_guidelines: TEST_LINES => [5, 12],
+    });
 
     #[test]
-    fn test_part_2() {
-        let segments = Segment::from_lines(TEST_LINES);
-        assert_eq!(part_2(&segments), 12);
+    fn test_invalid_segment() {
+        let err = expect_parse_error(Segment::from_lines("0,9 -> 5,9\n8,0 => 0,8"));
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.text, "8,0 => 0,8");
+        assert_eq!(err.kind, ParseErrorKind::InvalidFormat);
     }
 }
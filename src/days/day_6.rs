@@ -1,44 +1,66 @@
 //! This is the day 6 module.
 //! The instructions are available here: https://adventofcode.com/2021/day/6
 
-use std::fs;
-use std::path::Path;
+use std::io::BufRead;
 
-use crate::{DailySolution, Error};
+use crate::{
+    parse_number, read_content, split_fields, DailyInput, DailySolution, Error, ParseError,
+    ParseErrorKind, Part, PartAnswer,
+};
 
 pub struct Solution;
 
+impl Solution {
+    pub const DAY_NUMBER: u8 = 6;
+}
+
 impl DailySolution for Solution {
-    const DAY_NUMBER: u8 = 6;
+    fn day_number(&self) -> u8 {
+        Self::DAY_NUMBER
+    }
 
-    fn run_solution<P>(input_path: P) -> Result<(), Error>
-    where
-        P: AsRef<Path>,
-    {
-        let mut fish_net = FishNet::from_line(fs::read_to_string(input_path)?.as_str());
+    fn title(&self) -> &'static str {
+        "Lanternfish"
+    }
 
-        let part_1_result = part_1(&mut fish_net);
-        println!("Total fishes after 80 days: {}", part_1_result);
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error> {
+        let fish_net = FishNet::from_line(&read_content(reader)?)?;
 
-        let part_2_result = part_2(&mut fish_net);
-        println!("Total fishes after 256 days: {}", part_2_result);
-        Ok(())
+        Ok(Box::new(fish_net))
     }
 }
 
-fn part_1(fish_net: &mut FishNet) -> usize {
-    (0..80).for_each(|_| fish_net.roll_fishes());
+impl DailyInput for FishNet {
+    fn solve(&self, part: Part) -> Result<PartAnswer, Error> {
+        Ok(match part {
+            Part::One => PartAnswer::new(part, "Total fishes after 80 days", part_1(self)),
+            Part::Two => PartAnswer::new(part, "Total fishes after 256 days", part_2(self)),
+        })
+    }
 
-    fish_net.fishes.iter().sum()
+    fn record_count(&self) -> usize {
+        self.fishes.iter().sum()
+    }
+}
+
+fn part_1(fish_net: &FishNet) -> usize {
+    count_fishes_after(fish_net, 80)
+}
+
+fn part_2(fish_net: &FishNet) -> usize {
+    count_fishes_after(fish_net, 256)
 }
 
-fn part_2(fish_net: &mut FishNet) -> usize {
-    (80..256).for_each(|_| fish_net.roll_fishes());
+/// Count the fishes of the net after provided days, without altering the net.
+fn count_fishes_after(fish_net: &FishNet, days: usize) -> usize {
+    let mut fish_net = fish_net.clone();
+    (0..days).for_each(|_| fish_net.roll_fishes());
 
     fish_net.fishes.iter().sum()
 }
 
 /// A structure to keep tracking over fishes of each age
+#[derive(Clone)]
 struct FishNet {
     fishes: [usize; 9],
 }
@@ -50,22 +72,35 @@ impl FishNet {
         }
     }
 
-    fn from_line(line: &str) -> Self {
+    fn from_line(line: &str) -> Result<Self, Error> {
         let mut new_fish_net = Self::new();
 
-        for fish_age in line.trim().split(',').filter_map(|item| item.parse().ok()) {
-            new_fish_net.insert_fish(fish_age);
+        for (column, item) in split_fields(line.trim_end(), ',') {
+            let fish_age = parse_number(Solution::DAY_NUMBER, 1, column, item)?;
+            if !new_fish_net.insert_fish(fish_age) {
+                return Err(ParseError::new(
+                    Solution::DAY_NUMBER,
+                    1,
+                    column,
+                    item,
+                    ParseErrorKind::OutOfRange,
+                )
+                .into());
+            }
         }
 
-        new_fish_net
+        Ok(new_fish_net)
     }
 
-    fn insert_fish(&mut self, fish_age: usize) {
-        if fish_age >= 9 {
-            panic!("unexpected fish age {}", fish_age);
+    /// Insert a fish of provided age, returning false if there is no such age.
+    fn insert_fish(&mut self, fish_age: usize) -> bool {
+        match self.fishes.get_mut(fish_age) {
+            Some(count) => {
+                *count += 1;
+                true
+            }
+            None => false,
         }
-
-        self.fishes[fish_age] += 1;
     }
 
     fn roll_fishes(&mut self) {
@@ -80,14 +115,15 @@ impl FishNet {
 
 #[cfg(test)]
 mod test_day {
-    use super::FishNet;
+    use super::{part_1, part_2, FishNet};
+    use crate::{expect_parse_error, ParseErrorKind};
 
     /// Sample lines from guidelines
     const TEST_LINES: &str = "3,4,3,1,2";
 
     #[test]
     fn test_growth() {
-        let mut fish_net = FishNet::from_line(TEST_LINES);
+        let mut fish_net = FishNet::from_line(TEST_LINES).unwrap();
 
         for grow_line in [
             vec![2, 3, 2, 0, 1],
@@ -120,21 +156,23 @@ mod test_day {
         }
     }
 
-    #[test]
-    fn test_part_1() {
-        let mut fish_net = FishNet::from_line(TEST_LINES);
+    crate::example_tests!(super::Solution, {
+        test_guidelines: TEST_LINES => [5934, 26984457539],
+    });
 
-        (0..80).for_each(|_| fish_net.roll_fishes());
+    #[test]
+    fn test_independent_parts() {
+        let fish_net = FishNet::from_line(TEST_LINES).unwrap();
 
-        assert_eq!(fish_net.fishes.iter().sum::<usize>(), 5934);
+        assert_eq!(part_2(&fish_net), 26984457539);
+        assert_eq!(part_1(&fish_net), 5934);
     }
 
     #[test]
-    fn test_part_2() {
-        let mut fish_net = FishNet::from_line(TEST_LINES);
-
-        (0..256).for_each(|_| fish_net.roll_fishes());
-
-        assert_eq!(fish_net.fishes.iter().sum::<usize>(), 26984457539);
+    fn test_unexpected_fish_age() {
+        let err = expect_parse_error(FishNet::from_line("3,4,9,1,2"));
+        assert_eq!((err.line, err.column), (1, 5));
+        assert_eq!(err.text, "9");
+        assert_eq!(err.kind, ParseErrorKind::OutOfRange);
     }
 }
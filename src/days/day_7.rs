@@ -2,35 +2,48 @@
 //! The instructions are available here: https://adventofcode.com/2021/day/7
 
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
+use std::io::BufRead;
 
-use crate::{DailySolution, Error};
+use crate::{
+    parse_number, read_content, split_fields, DailyInput, DailySolution, Error, ParseError,
+    ParseErrorKind, Part, PartAnswer,
+};
 
 pub struct Solution;
 
+impl Solution {
+    pub const DAY_NUMBER: u8 = 7;
+}
+
 impl DailySolution for Solution {
-    const DAY_NUMBER: u8 = 7;
-
-    fn run_solution<P>(input_path: P) -> Result<(), Error>
-    where
-        P: AsRef<Path>,
-    {
-        let crab_positions = get_crab_positions(fs::read_to_string(input_path)?.as_str());
-
-        let (best_position, fuel_cost) = part_1(&crab_positions);
-        println!(
-            "Best position is {} for {} fuel units",
-            best_position, fuel_cost
-        );
-
-        let (best_position_2, fuel_cost_2) = part_2(&crab_positions);
-        println!(
-            "New best position is {} for {} fuel units",
-            best_position_2, fuel_cost_2
-        );
-
-        Ok(())
+    fn day_number(&self) -> u8 {
+        Self::DAY_NUMBER
+    }
+
+    fn title(&self) -> &'static str {
+        "The Treachery of Whales"
+    }
+
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error> {
+        let crab_positions = get_crab_positions(&read_content(reader)?)?;
+
+        Ok(Box::new(CrabPositions(crab_positions)))
+    }
+}
+
+/// The count of crabs at each horizontal position.
+struct CrabPositions(HashMap<usize, usize>);
+
+impl DailyInput for CrabPositions {
+    fn solve(&self, part: Part) -> Result<PartAnswer, Error> {
+        Ok(match part {
+            Part::One => PartAnswer::new(part, "Best position and fuel units", part_1(&self.0)),
+            Part::Two => PartAnswer::new(part, "New best position and fuel units", part_2(&self.0)),
+        })
+    }
+
+    fn record_count(&self) -> usize {
+        self.0.values().sum()
     }
 }
 
@@ -64,13 +77,26 @@ fn part_2(crab_positions: &HashMap<usize, usize>) -> (usize, usize) {
         .unwrap()
 }
 
-fn get_crab_positions(line: &str) -> HashMap<usize, usize> {
+fn get_crab_positions(line: &str) -> Result<HashMap<usize, usize>, Error> {
     let mut positions = HashMap::new();
-    line.trim()
-        .split(',')
-        .for_each(|item| *positions.entry(item.parse::<usize>().unwrap()).or_insert(0) += 1);
+    for (column, item) in split_fields(line.trim_end(), ',') {
+        *positions
+            .entry(parse_number(Solution::DAY_NUMBER, 1, column, item)?)
+            .or_insert(0) += 1;
+    }
 
-    positions
+    if positions.is_empty() {
+        return Err(ParseError::new(
+            Solution::DAY_NUMBER,
+            1,
+            1,
+            line.trim(),
+            ParseErrorKind::EmptyInput,
+        )
+        .into());
+    }
+
+    Ok(positions)
 }
 
 fn compute_position_cost(origin: usize, destination: usize) -> usize {
@@ -84,7 +110,8 @@ fn compute_position_cost(origin: usize, destination: usize) -> usize {
 
 #[cfg(test)]
 mod test_day {
-    use super::{compute_position_cost, get_crab_positions, part_1, part_2};
+    use super::{compute_position_cost, get_crab_positions};
+    use crate::{expect_parse_error, ParseErrorKind};
 
     const TEST_LINE: &str = "16,1,2,0,4,2,7,1,2,14";
 
@@ -102,19 +129,17 @@ mod test_day {
         assert_eq!(compute_position_cost(14, 5), 45);
     }
 
-    #[test]
-    fn test_part_1() {
-        let positions = get_crab_positions(TEST_LINE);
-        let (best_position, fuel_cost) = part_1(&positions);
-        assert_eq!(best_position, 2usize);
-        assert_eq!(fuel_cost, 37usize);
-    }
+    crate::example_tests!(super::Solution, {
+        test_guidelines: TEST_LINE => [(2, 37), (5, 168)],
+    });
 
     #[test]
-    fn test_part_2() {
-        let positions = get_crab_positions(TEST_LINE);
-        let (best_position, fuel_cost) = part_2(&positions);
-        assert_eq!(best_position, 5usize);
-        assert_eq!(fuel_cost, 168usize);
+    fn test_invalid_positions() {
+        let err = expect_parse_error(get_crab_positions("16,1,two,0"));
+        assert_eq!((err.line, err.column), (1, 6));
+        assert_eq!(err.kind, ParseErrorKind::InvalidNumber);
+
+        let err = expect_parse_error(get_crab_positions("\n"));
+        assert_eq!(err.kind, ParseErrorKind::EmptyInput);
     }
 }
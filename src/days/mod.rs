@@ -1,9 +1,100 @@
 //! This module stores daily solutions.
 
+#[cfg(feature = "day_1")]
 pub mod day_1;
+#[cfg(feature = "day_2")]
 pub mod day_2;
+#[cfg(feature = "day_3")]
 pub mod day_3;
+#[cfg(feature = "day_4")]
 pub mod day_4;
+#[cfg(feature = "day_5")]
 pub mod day_5;
+#[cfg(feature = "day_6")]
 pub mod day_6;
+#[cfg(feature = "day_7")]
 pub mod day_7;
+
+use crate::DailySolution;
+
+/// Every implemented daily solution enabled by its feature, sorted by day number.
+pub static SOLUTIONS: &[&dyn DailySolution] = &[
+    #[cfg(feature = "day_1")]
+    &day_1::Solution,
+    #[cfg(feature = "day_2")]
+    &day_2::Solution,
+    #[cfg(feature = "day_3")]
+    &day_3::Solution,
+    #[cfg(feature = "day_4")]
+    &day_4::Solution,
+    #[cfg(feature = "day_5")]
+    &day_5::Solution,
+    #[cfg(feature = "day_6")]
+    &day_6::Solution,
+    #[cfg(feature = "day_7")]
+    &day_7::Solution,
+];
+
+/// Get the solution of provided day, if it is implemented.
+pub fn get_solution(day_number: u8) -> Option<&'static dyn DailySolution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day_number() == day_number)
+        .copied()
+}
+
+#[cfg(test)]
+mod test_days {
+    use super::SOLUTIONS;
+
+    #[test]
+    fn test_solutions_order() {
+        assert!(SOLUTIONS
+            .windows(2)
+            .all(|pair| pair[0].day_number() < pair[1].day_number()));
+    }
+
+    #[test]
+    #[cfg(feature = "day_7")]
+    fn test_get_solution() {
+        let solution = super::get_solution(7).unwrap();
+        assert_eq!(solution.day_number(), 7);
+        assert_eq!(solution.title(), "The Treachery of Whales");
+        assert_eq!(solution.url(), "https://adventofcode.com/2021/day/7");
+
+        assert!(super::get_solution(0).is_none());
+        assert!(super::get_solution(26).is_none());
+    }
+
+    #[test]
+    #[cfg(all(feature = "day_1", feature = "day_7"))]
+    fn test_run_str() {
+        use crate::{Answer, DailySolution, Part};
+
+        let part_answers = super::day_1::Solution
+            .run_str(
+                "199\n200\n208\n210\n200\n207\n240\n269\n260\n263",
+                &Part::ALL,
+            )
+            .unwrap();
+        assert_eq!(part_answers[0].answer, Answer::Number(7));
+        assert_eq!(part_answers[1].answer, Answer::Number(5));
+
+        let part_answers = super::get_solution(7)
+            .unwrap()
+            .run_str("16,1,2,0,4,2,7,1,2,14", &[Part::Two])
+            .unwrap();
+        assert_eq!(part_answers.len(), 1);
+        assert_eq!(part_answers[0].part, Part::Two);
+        assert_eq!(part_answers[0].answer, Answer::from((5, 168)));
+    }
+
+    #[test]
+    #[cfg(feature = "day_7")]
+    fn test_run_str_parse_error() {
+        use crate::{DailySolution, Error, Part};
+
+        let run_result = super::day_7::Solution.run_str("16,1,x", &Part::ALL);
+        assert!(matches!(run_result, Err(Error::ParseError(_))));
+    }
+}
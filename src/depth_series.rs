@@ -0,0 +1,283 @@
+//! This module analyses series of sonar depth measurements, such as the day 1 reports.
+//! The measurements are summed over sliding windows, as in day 1, and each window is compared to
+//! the previous one. Measurements are fed one at a time, so that only the current window is kept
+//! whatever the length of the series.
+
+use std::fmt::Write;
+use std::io::BufRead;
+
+use crate::days::day_1::{read_depths, Anomaly, ParseMode, SlidingWindow, WindowChange};
+use crate::Error;
+
+/// The statistics of the sliding windows of a given size over a depth series.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowStatistics {
+    /// The number of measurements summed by each window.
+    pub window: usize,
+    /// The number of complete windows.
+    pub windows: usize,
+    pub increases: usize,
+    pub decreases: usize,
+    /// The number of windows whose sum is equal to the sum of the previous window.
+    pub plateaus: usize,
+    /// The highest number of consecutive increases.
+    pub longest_increasing_run: usize,
+    /// The highest decrease of the sum between two consecutive windows, if any.
+    pub max_drop: Option<usize>,
+    /// The lowest average depth of a window, if any.
+    pub min_average: Option<f64>,
+    /// The highest average depth of a window, if any.
+    pub max_average: Option<f64>,
+}
+
+impl WindowStatistics {
+    fn new(window: usize) -> Self {
+        Self {
+            window,
+            windows: 0,
+            increases: 0,
+            decreases: 0,
+            plateaus: 0,
+            longest_increasing_run: 0,
+            max_drop: None,
+            min_average: None,
+            max_average: None,
+        }
+    }
+}
+
+/// Compute the statistics of sliding windows of a given size over depth measurements, fed one at
+/// a time.
+#[derive(Clone, Debug)]
+pub struct DepthAnalyzer {
+    window: SlidingWindow,
+    increasing_run: usize,
+    statistics: WindowStatistics,
+}
+
+impl DepthAnalyzer {
+    /// An analyzer over windows of provided size, which must not be 0.
+    pub fn new(size: usize) -> Self {
+        Self {
+            window: SlidingWindow::new(size),
+            increasing_run: 0,
+            statistics: WindowStatistics::new(size),
+        }
+    }
+
+    /// Add the next measurement, returning the average depth of the window it completes, if any.
+    pub fn push(&mut self, depth: usize) -> Option<f64> {
+        if let Some(change) = self.window.push(depth) {
+            self.record(change);
+        }
+        if !self.window.is_complete() {
+            return None;
+        }
+
+        let average = self.window.sum() as f64 / self.window.size() as f64;
+        let statistics = &mut self.statistics;
+        statistics.windows += 1;
+        statistics.min_average = Some(statistics.min_average.map_or(average, |x| x.min(average)));
+        statistics.max_average = Some(statistics.max_average.map_or(average, |x| x.max(average)));
+
+        Some(average)
+    }
+
+    /// Record the change of a window from the previous one.
+    fn record(&mut self, change: WindowChange) {
+        let statistics = &mut self.statistics;
+
+        match change {
+            WindowChange::Increase(_) => {
+                statistics.increases += 1;
+                self.increasing_run += 1;
+                statistics.longest_increasing_run =
+                    statistics.longest_increasing_run.max(self.increasing_run);
+            }
+            WindowChange::Decrease(drop) => {
+                self.increasing_run = 0;
+                statistics.decreases += 1;
+                statistics.max_drop = Some(statistics.max_drop.map_or(drop, |x| x.max(drop)));
+            }
+            WindowChange::Plateau => {
+                self.increasing_run = 0;
+                statistics.plateaus += 1;
+            }
+        }
+    }
+
+    /// The statistics of the measurements fed so far.
+    pub fn statistics(&self) -> &WindowStatistics {
+        &self.statistics
+    }
+}
+
+/// Compute the statistics of sliding windows of provided size over depth measurements.
+pub fn analyze<I>(depths: I, window: usize) -> WindowStatistics
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut analyzer = DepthAnalyzer::new(window);
+    for depth in depths {
+        analyzer.push(depth);
+    }
+
+    analyzer.statistics
+}
+
+/// Compute the statistics of sliding windows of each provided size in a single pass over a depth
+/// report, handling its bad readings with provided mode, and return the tolerated bad readings.
+pub fn analyze_report<R>(
+    reader: R,
+    windows: &[usize],
+    mode: ParseMode,
+) -> Result<(Vec<WindowStatistics>, Vec<Anomaly>), Error>
+where
+    R: BufRead,
+{
+    let mut analyzers: Vec<DepthAnalyzer> = windows
+        .iter()
+        .map(|&size| DepthAnalyzer::new(size))
+        .collect();
+    let anomalies = read_depths(reader, mode, |depth| {
+        for analyzer in analyzers.iter_mut() {
+            analyzer.push(depth);
+        }
+    })?;
+
+    let statistics = analyzers
+        .into_iter()
+        .map(|analyzer| analyzer.statistics)
+        .collect();
+    Ok((statistics, anomalies))
+}
+
+/// The moving average depth of the sliding windows of provided size over depth measurements.
+pub fn moving_averages<I>(depths: I, window: usize) -> impl Iterator<Item = f64>
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut analyzer = DepthAnalyzer::new(window);
+
+    depths
+        .into_iter()
+        .filter_map(move |depth| analyzer.push(depth))
+}
+
+/// Format the statistics of several window sizes as a table, one window size per row.
+pub fn report_table(statistics: &[WindowStatistics]) -> String {
+    let format_optional = |value: Option<String>| value.unwrap_or_else(|| String::from("-"));
+    let mut table = format!(
+        "{:>6} {:>8} {:>9} {:>9} {:>8} {:>11} {:>8} {:>11} {:>11}\n",
+        "Window",
+        "Windows",
+        "Increases",
+        "Decreases",
+        "Plateaus",
+        "Longest run",
+        "Max drop",
+        "Min average",
+        "Max average"
+    );
+
+    for statistics in statistics {
+        writeln!(
+            table,
+            "{:>6} {:>8} {:>9} {:>9} {:>8} {:>11} {:>8} {:>11} {:>11}",
+            statistics.window,
+            statistics.windows,
+            statistics.increases,
+            statistics.decreases,
+            statistics.plateaus,
+            statistics.longest_increasing_run,
+            format_optional(statistics.max_drop.map(|drop| drop.to_string())),
+            format_optional(
+                statistics
+                    .min_average
+                    .map(|average| format!("{:.2}", average))
+            ),
+            format_optional(
+                statistics
+                    .max_average
+                    .map(|average| format!("{:.2}", average))
+            )
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod test_depth_series {
+    use super::{analyze, analyze_report, moving_averages, report_table, WindowStatistics};
+    use crate::days::day_1::ParseMode;
+
+    /// Sample depths from day 1 guidelines
+    const TEST_DEPTHS: [usize; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_analyze() {
+        assert_eq!(
+            analyze(TEST_DEPTHS, 1),
+            WindowStatistics {
+                window: 1,
+                windows: 10,
+                increases: 7,
+                decreases: 2,
+                plateaus: 0,
+                longest_increasing_run: 3,
+                max_drop: Some(10),
+                min_average: Some(199.0),
+                max_average: Some(269.0),
+            }
+        );
+
+        let statistics = analyze(TEST_DEPTHS, 3);
+        assert_eq!(statistics.windows, 8);
+        assert_eq!(
+            (
+                statistics.increases,
+                statistics.decreases,
+                statistics.plateaus
+            ),
+            (5, 1, 1)
+        );
+        assert_eq!(statistics.longest_increasing_run, 4);
+        assert_eq!(statistics.max_drop, Some(1));
+        assert_eq!(statistics.max_average, Some(264.0));
+
+        assert_eq!(analyze(TEST_DEPTHS, 11), WindowStatistics::new(11));
+    }
+
+    #[test]
+    fn test_analyze_report() {
+        let (statistics, anomalies) =
+            analyze_report("1\n\n3\n2\n".as_bytes(), &[1], ParseMode::Interpolate).unwrap();
+        assert_eq!((statistics[0].windows, statistics[0].increases), (4, 2));
+        assert_eq!(anomalies[0].replacement, Some(2));
+
+        assert!(analyze_report("1\n\n3\n".as_bytes(), &[1], ParseMode::Strict).is_err());
+    }
+
+    #[test]
+    fn test_moving_averages() {
+        assert_eq!(
+            moving_averages(TEST_DEPTHS, 4).collect::<Vec<f64>>(),
+            vec![204.25, 204.5, 206.25, 214.25, 229.0, 244.0, 258.0]
+        );
+    }
+
+    #[test]
+    fn test_report_table() {
+        let table = report_table(&[analyze(TEST_DEPTHS, 3), analyze([1, 2], 3)]);
+
+        assert_eq!(
+            table,
+            "Window  Windows Increases Decreases Plateaus Longest run Max drop Min average Max average
+     3        8         5         1        1           4        1      202.33      264.00
+     3        0         0         0        0           0        -           -           -
+"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! This module is a test harness checking daily solutions over the examples of the puzzles.
+//! Examples are registered with the [`example_tests`](crate::example_tests) macro, which
+//! generates a test for each of them against the public [`DailySolution`] API.
+
+use crate::{Answer, DailySolution, Part};
+
+/// Run a solution over an example input and check the answer of each part, in parts order.
+/// When fewer answers than parts are expected, only the first parts are run.
+///
+/// # Panics
+///
+/// Panics if the example cannot be solved or if an answer differs from the expected one.
+pub fn check_example(solution: &dyn DailySolution, input: &str, expected_answers: &[Answer]) {
+    let parts = &Part::ALL[..expected_answers.len().min(Part::ALL.len())];
+
+    let part_answers = solution
+        .run_str(input, parts)
+        .unwrap_or_else(|err| panic!("day {} failed over example: {}", solution.day_number(), err));
+
+    for (part_answer, expected_answer) in part_answers.iter().zip(expected_answers) {
+        assert_eq!(
+            &part_answer.answer,
+            expected_answer,
+            "wrong answer for day {} part {}",
+            solution.day_number(),
+            part_answer.part
+        );
+    }
+}
+
+/// Generate a test for each example of a daily solution.
+/// Each example is named, and lists the expected answer of each part in order.
+/// Attributes such as `#[ignore]` can precede the name of an example.
+///
+/// The generated tests only exist in test builds, where each of them calls [`check_example`], so
+/// the example below is compiled but not run:
+///
+/// ```no_run
+/// mod test_day {
+///     aoc_2021_rs::example_tests!(aoc_2021_rs::days::day_7::Solution, {
+///         test_guidelines: "16,1,2,0,4,2,7,1,2,14" => [(2, 37), (5, 168)],
+///     });
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:expr, { $($(#[$meta:meta])* $name:ident: $input:expr => [$($answer:expr),+ $(,)?]),+ $(,)? }) => {
+        $(
+            #[test]
+            $(#[$meta])*
+            fn $name() {
+                $crate::harness::check_example(
+                    &$solution,
+                    $input,
+                    &[$($crate::Answer::from($answer)),+],
+                );
+            }
+        )+
+    };
+}
+
+#[cfg(all(test, feature = "day_1"))]
+mod test_harness {
+    use super::check_example;
+    use crate::days::day_1;
+    use crate::Answer;
+
+    crate::example_tests!(day_1::Solution, {
+        test_single_part: "1\n2\n3\n2" => [2],
+        test_both_parts: "1\n2\n3\n2\n5" => [3, 2],
+        #[should_panic(expected = "wrong answer for day 1 part 1")]
+        test_attributes: "1\n2" => [0],
+    });
+
+    #[test]
+    #[should_panic(expected = "wrong answer for day 1 part 2")]
+    fn test_wrong_answer() {
+        check_example(
+            &day_1::Solution,
+            "1\n2\n3\n2\n5",
+            &[Answer::Number(3), Answer::Number(1)],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "day 1 failed over example")]
+    fn test_invalid_example() {
+        check_example(&day_1::Solution, "1\nx", &[Answer::Number(1)]);
+    }
+}
@@ -0,0 +1,194 @@
+//! This module locates the puzzle inputs of each day.
+//! An input is read from a file or from the standard input, noted `-`.
+//! Unless a file is provided for a day, the file `daily_input_<day_num>` is looked up into the
+//! inputs directory, which is resolved in this order:
+//! - the directory explicitly provided to the [`InputLocator`],
+//! - the directory named by the `AOC_INPUTS_DIR` environment variable,
+//! - the *aoc_inputs* directory stored into current directory.
+//!
+//! Several named input sets, for instance one per account, can be stored as subdirectories of
+//! the inputs directory, and selected in place of the inputs directory itself.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Environment variable naming the inputs directory.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// The inputs directory used when none is explicitly provided.
+pub fn inputs_directory() -> PathBuf {
+    match std::env::var_os(INPUTS_DIR_VAR) {
+        Some(directory) if !directory.is_empty() => PathBuf::from(directory),
+        _ => std::env::current_dir()
+            .unwrap_or_default()
+            .join("aoc_inputs"),
+    }
+}
+
+/// The name of the input file of provided day.
+pub fn input_file_name(day: u8) -> String {
+    format!("daily_input_{}", day)
+}
+
+/// Whether provided name can be used as an input set, which is a plain directory name.
+pub fn is_valid_set_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Where a puzzle input is read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Open the source as a buffered reader.
+    pub fn open(&self) -> Result<Box<dyn BufRead>, Error> {
+        Ok(match self {
+            Self::File(path) => Box::new(BufReader::new(fs::File::open(path)?)),
+            Self::Stdin => Box::new(io::stdin().lock()),
+        })
+    }
+
+    /// Read the whole content of the source.
+    pub fn read_to_string(&self) -> Result<String, Error> {
+        let mut content = String::new();
+        self.open()?.read_to_string(&mut content)?;
+
+        Ok(content)
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(String::from("empty input path")),
+            "-" => Ok(Self::Stdin),
+            path => Ok(Self::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => f.write_str("-"),
+        }
+    }
+}
+
+/// Resolve the input source of each day, with optional overrides.
+#[derive(Clone, Debug, Default)]
+pub struct InputLocator {
+    directory: Option<PathBuf>,
+    set: Option<String>,
+    day_sources: HashMap<u8, InputSource>,
+}
+
+impl InputLocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look up input files into provided directory instead of the default one.
+    pub fn with_directory(mut self, directory: PathBuf) -> Self {
+        self.directory = Some(directory);
+        self
+    }
+
+    /// Look up input files into provided named set of the inputs directory.
+    pub fn with_set(mut self, set: String) -> Self {
+        self.set = Some(set);
+        self
+    }
+
+    /// Use provided source for the input of a day.
+    pub fn with_day_source(mut self, day: u8, source: InputSource) -> Self {
+        self.day_sources.insert(day, source);
+        self
+    }
+
+    /// The directory where input files are looked up, within the inputs directory.
+    pub fn directory(&self) -> PathBuf {
+        let directory = self.directory.clone().unwrap_or_else(inputs_directory);
+
+        match &self.set {
+            Some(set) => directory.join(set),
+            None => directory,
+        }
+    }
+
+    /// The input source of provided day.
+    pub fn locate(&self, day: u8) -> InputSource {
+        match self.day_sources.get(&day) {
+            Some(source) => source.clone(),
+            None => InputSource::File(self.directory().join(input_file_name(day))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_input {
+    use std::path::PathBuf;
+
+    use super::{is_valid_set_name, InputLocator, InputSource};
+
+    #[test]
+    fn test_input_source_from_str() {
+        assert_eq!("-".parse::<InputSource>(), Ok(InputSource::Stdin));
+        assert_eq!(
+            "inputs/day_1".parse::<InputSource>(),
+            Ok(InputSource::File(PathBuf::from("inputs/day_1")))
+        );
+        assert!("".parse::<InputSource>().is_err());
+    }
+
+    #[test]
+    fn test_locate() {
+        let locator = InputLocator::new()
+            .with_directory(PathBuf::from("/tmp/inputs"))
+            .with_day_source(2, InputSource::Stdin)
+            .with_day_source(3, InputSource::File(PathBuf::from("day_3.txt")));
+
+        assert_eq!(
+            locator.locate(1),
+            InputSource::File(PathBuf::from("/tmp/inputs/daily_input_1"))
+        );
+        assert_eq!(locator.locate(2), InputSource::Stdin);
+        assert_eq!(
+            locator.locate(3),
+            InputSource::File(PathBuf::from("day_3.txt"))
+        );
+
+        let locator = InputLocator::new()
+            .with_directory(PathBuf::from("/tmp/inputs"))
+            .with_set(String::from("alice"));
+        assert_eq!(
+            locator.locate(1),
+            InputSource::File(PathBuf::from("/tmp/inputs/alice/daily_input_1"))
+        );
+    }
+
+    #[test]
+    fn test_set_name() {
+        assert!(is_valid_set_name("alice"));
+        assert!(is_valid_set_name("team-2.bob"));
+        assert!(!is_valid_set_name(""));
+        assert!(!is_valid_set_name(".."));
+        assert!(!is_valid_set_name("a/b"));
+    }
+}
@@ -1,15 +1,47 @@
 #![deny(clippy::all)]
 
 use std::fmt::{Display, Formatter};
-use std::io;
-use std::path::Path;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+use crate::input::InputSource;
+
+#[cfg(feature = "tools")]
+pub mod bench;
+#[cfg(feature = "tools")]
+pub mod cache;
 pub mod days;
+#[cfg(feature = "day_1")]
+pub mod depth_series;
+pub mod harness;
+pub mod input;
+pub mod log;
+#[cfg(feature = "tools")]
+pub mod memory;
+#[cfg(feature = "tools")]
+pub mod progress;
+#[cfg(feature = "tools")]
+pub mod report;
+#[cfg(feature = "tools")]
+pub mod scaffold;
+#[cfg(feature = "tools")]
+pub mod submit;
+#[cfg(feature = "tools")]
+pub mod verify;
+#[cfg(feature = "tools")]
+pub mod watch;
 
 /// An abstract error enum to handle errors.
 #[derive(Debug)]
 pub enum Error {
     IOError(io::Error),
+    ParseError(ParseError),
+    SolveError(SolveError),
+    /// A file or a registration would be replaced, described by the value.
+    AlreadyExists(String),
+    /// An endpoint answered with something else than a page, such as an HTTP error status.
+    UnexpectedResponse(String),
 }
 
 impl From<io::Error> for Error {
@@ -22,34 +54,603 @@ impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::IOError(err) => std::fmt::Display::fmt(err, f),
+            Self::ParseError(err) => std::fmt::Display::fmt(err, f),
+            Self::SolveError(err) => std::fmt::Display::fmt(err, f),
+            Self::AlreadyExists(what) => write!(f, "{} already exists", what),
+            Self::UnexpectedResponse(response) => write!(f, "unexpected response '{}'", response),
         }
     }
 }
 
 impl std::error::Error for Error {}
 
-/// A trait to run a daily solution. Each daily solution could have an input file to use.
-/// This input file can be found into the *aoc_inputs* directory stored into current directory.
-pub trait DailySolution {
-    const DAY_NUMBER: u8;
+/// The reason why a piece of input has been rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// The input does not contain anything to parse.
+    EmptyInput,
+    /// A line does not follow the expected format.
+    InvalidFormat,
+    /// A number could not be parsed.
+    InvalidNumber,
+    /// A block of lines or values does not have the expected size.
+    InvalidSize,
+    /// A value is parsed but outside of its allowed range.
+    OutOfRange,
+    /// A character is not part of the expected alphabet.
+    UnexpectedChar,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Self::EmptyInput => "empty input",
+            Self::InvalidFormat => "invalid format",
+            Self::InvalidNumber => "invalid number",
+            Self::InvalidSize => "invalid size",
+            Self::OutOfRange => "value out of range",
+            Self::UnexpectedChar => "unexpected char",
+        };
+        f.write_str(description)
+    }
+}
+
+/// A located parse failure over a daily input, or over a file shared by every day.
+/// Line and column numbers start at 1, as displayed by text editors.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// The day of the parsed input, or None for a file shared by every day.
+    pub day: Option<u8>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new<D, S>(day: D, line: usize, column: usize, text: S, kind: ParseErrorKind) -> Self
+    where
+        D: Into<Option<u8>>,
+        S: Into<String>,
+    {
+        Self {
+            day: day.into(),
+            line,
+            column,
+            text: text.into(),
+            kind,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::ParseError(error)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {} '{}'",
+            self.line, self.column, self.kind, self.text
+        )
+    }
+}
+
+/// The reason why a well-formed input cannot be solved.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SolveErrorKind {
+    /// The puzzle has no solution over the input.
+    NoSolution,
+    /// A computed value does not fit into its type.
+    Overflow,
+}
+
+impl Display for SolveErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Self::NoSolution => "no solution",
+            Self::Overflow => "arithmetic overflow",
+        };
+        f.write_str(description)
+    }
+}
+
+/// A failure of a solution over a parsed daily input, located at the line triggering it if any.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolveError {
+    pub day: u8,
+    pub line: Option<usize>,
+    pub text: String,
+    pub kind: SolveErrorKind,
+}
+
+impl SolveError {
+    pub fn new<S>(day: u8, line: Option<usize>, text: S, kind: SolveErrorKind) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            day,
+            line,
+            text: text.into(),
+            kind,
+        }
+    }
+}
+
+impl From<SolveError> for Error {
+    fn from(error: SolveError) -> Self {
+        Self::SolveError(error)
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {}", self.day)?;
+        if let Some(line) = self.line {
+            write!(f, ", line {}", line)?;
+        }
+        write!(f, ": {} '{}'", self.kind, self.text)
+    }
+}
+
+/// Split a line over a separator and yield each non-empty field, trimmed, with its column.
+#[cfg(any(
+    feature = "day_2",
+    feature = "day_4",
+    feature = "day_6",
+    feature = "day_7",
+    feature = "tools"
+))]
+pub(crate) fn split_fields(line: &str, separator: char) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
+
+    line.split(separator).filter_map(move |field| {
+        let field_offset = offset;
+        offset += field.len() + separator.len_utf8();
+
+        let trimmed = field.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            let leading_spaces = field.len() - field.trim_start().len();
+            Some((field_offset + leading_spaces + 1, trimmed))
+        }
+    })
+}
+
+/// Read the whole content of provided reader.
+#[cfg(any(
+    feature = "day_2",
+    feature = "day_5",
+    feature = "day_6",
+    feature = "day_7"
+))]
+pub(crate) fn read_content(reader: &mut dyn BufRead) -> Result<String, Error> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+
+    Ok(content)
+}
+
+/// Parse a number found at provided location of a daily input.
+#[cfg(any(
+    feature = "day_1",
+    feature = "day_2",
+    feature = "day_4",
+    feature = "day_5",
+    feature = "day_6",
+    feature = "day_7"
+))]
+pub(crate) fn parse_number<T>(day: u8, line: usize, column: usize, text: &str) -> Result<T, Error>
+where
+    T: FromStr,
+{
+    text.parse::<T>()
+        .map_err(|_| ParseError::new(day, line, column, text, ParseErrorKind::InvalidNumber).into())
+}
+
+/// Parse a `<day>:<part>` key, such as `7:2`, found at provided location of a file shared by
+/// every day. Days outside the event calendar are out of range.
+#[cfg(feature = "tools")]
+pub fn parse_day_part(line: usize, column: usize, key: &str) -> Result<(u8, Part), ParseError> {
+    let error = |column: usize, text: &str, kind: ParseErrorKind| {
+        ParseError::new(None, line, column, text, kind)
+    };
+    let (day, part) = key
+        .split_once(':')
+        .ok_or_else(|| error(column, key, ParseErrorKind::InvalidFormat))?;
+
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| error(column, day, ParseErrorKind::InvalidNumber))?;
+    if !(1..=progress::CALENDAR_DAYS).contains(&day) {
+        return Err(error(column, &day.to_string(), ParseErrorKind::OutOfRange));
+    }
+    let part_column = column + key.find(':').unwrap() + 1;
+    let part = part
+        .parse::<Part>()
+        .map_err(|_| error(part_column, part, ParseErrorKind::OutOfRange))?;
+
+    Ok((day, part))
+}
+
+/// An entry of a file recording a value per day part, such as the expected answers.
+#[cfg(feature = "tools")]
+pub(crate) struct DayPartEntry<'a> {
+    pub line: usize,
+    pub day: u8,
+    pub part: Part,
+    /// The value following the key, trimmed, which may be empty.
+    pub value: &'a str,
+    pub value_column: usize,
+}
+
+/// Read the `<day>:<part> <value>` entries of a file shared by every day, skipping empty lines and
+/// lines starting with `#`.
+#[cfg(feature = "tools")]
+pub(crate) fn day_part_entries(
+    content: &str,
+) -> impl Iterator<Item = Result<DayPartEntry<'_>, Error>> {
+    content.lines().enumerate().filter_map(|(index, line)| {
+        let key_offset = line.len() - line.trim_start().len();
+        let entry = line.trim();
+        if entry.is_empty() || entry.starts_with('#') {
+            return None;
+        }
+
+        let (key, rest) = entry.split_once(' ').unwrap_or((entry, ""));
+        let value_offset = key_offset + key.len() + 1 + rest.len() - rest.trim_start().len();
+        let entry =
+            parse_day_part(index + 1, key_offset + 1, key).map(|(day, part)| DayPartEntry {
+                line: index + 1,
+                day,
+                part,
+                value: rest.trim(),
+                value_column: value_offset + 1,
+            });
+
+        Some(entry.map_err(Error::from))
+    })
+}
+
+/// The parse error of a result expected to be one, for tests.
+#[cfg(all(
+    test,
+    any(
+        feature = "day_1",
+        feature = "day_2",
+        feature = "day_3",
+        feature = "day_4",
+        feature = "day_5",
+        feature = "day_6",
+        feature = "day_7",
+        feature = "tools"
+    )
+))]
+pub(crate) fn expect_parse_error<T>(result: Result<T, Error>) -> ParseError {
+    match result {
+        Err(Error::ParseError(err)) => err,
+        Err(err) => panic!("expected a parse error, found '{}'", err),
+        Ok(_) => panic!("expected a parse error"),
+    }
+}
+
+/// The solve error of a result expected to be one, for tests.
+#[cfg(all(test, any(feature = "day_2", feature = "day_3", feature = "day_4")))]
+pub(crate) fn expect_solve_error<T>(result: Result<T, Error>) -> SolveError {
+    match result {
+        Err(Error::SolveError(err)) => err,
+        Err(err) => panic!("expected a solve error, found '{}'", err),
+        Ok(_) => panic!("expected a solve error"),
+    }
+}
+
+/// The value found for a puzzle part.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Number(usize),
+    /// A number which may be negative.
+    Signed(isize),
+    Text(String),
+    Tuple(Vec<Answer>),
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl<A, B> From<(A, B)> for Answer
+where
+    A: Into<Answer>,
+    B: Into<Answer>,
+{
+    fn from((first, second): (A, B)) -> Self {
+        Self::Tuple(vec![first.into(), second.into()])
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{}", value),
+            Self::Signed(value) => write!(f, "{}", value),
+            Self::Text(value) => write!(f, "{}", value),
+            Self::Tuple(values) => {
+                write!(f, "(")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// A part of a daily puzzle.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Every puzzle part, in solving order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            _ => Err(format!("unknown part {}, expected 1 or 2", value)),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<u8>()
+            .map_err(|_| format!("invalid part '{}', expected 1 or 2", s))?
+            .try_into()
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
 
-    /// Run the solution over provided input path.
-    fn run_solution<P>(input_path: P) -> Result<(), Error>
+/// The answer of a puzzle part, with a label describing what has been computed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartAnswer {
+    pub part: Part,
+    pub label: &'static str,
+    pub answer: Answer,
+}
+
+impl PartAnswer {
+    pub fn new<A>(part: Part, label: &'static str, answer: A) -> Self
     where
-        P: AsRef<Path>;
-
-    /// Run the solution over default input path.
-    fn run() -> Result<(), Error> {
-        let default_input_path = std::env::current_dir()
-            .unwrap()
-            .join("aoc_inputs")
-            .join(format!("daily_input_{}", Self::DAY_NUMBER));
-
-        println!(
-            "Start solution for day {} over input file '{}'",
-            Self::DAY_NUMBER,
-            default_input_path.display()
+        A: Into<Answer>,
+    {
+        Self {
+            part,
+            label,
+            answer: answer.into(),
+        }
+    }
+}
+
+impl Display for PartAnswer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.label, self.answer)
+    }
+}
+
+/// A trait to run a daily solution. Each daily solution could have an input file to use.
+/// By default, this input file can be found into the *aoc_inputs* directory stored into current
+/// directory, see the [`input`] module for other locations.
+///
+/// The trait is object safe, so that every solution can be registered into
+/// [`days::SOLUTIONS`] and used as `&dyn DailySolution`.
+pub trait DailySolution: Sync {
+    /// The day number of the puzzle in the event calendar.
+    fn day_number(&self) -> u8;
+
+    /// The title of the puzzle.
+    fn title(&self) -> &'static str;
+
+    /// The address of the puzzle instructions.
+    fn url(&self) -> String {
+        format!("https://adventofcode.com/2021/day/{}", self.day_number())
+    }
+
+    /// Parse the puzzle input from provided reader, which can then solve each part independently.
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error>;
+
+    /// Parse the puzzle input read from provided source.
+    fn parse_source(&self, source: &InputSource) -> Result<Box<dyn DailyInput>, Error> {
+        self.parse_input(&mut source.open()?)
+    }
+
+    /// Run a single part of the solution over provided input source.
+    fn run_part(&self, source: &InputSource, part: Part) -> Result<PartAnswer, Error> {
+        self.parse_source(source)?.solve(part)
+    }
+
+    /// Run the requested parts of the solution over the input read from provided reader.
+    fn run_reader(
+        &self,
+        reader: &mut dyn BufRead,
+        parts: &[Part],
+    ) -> Result<Vec<PartAnswer>, Error> {
+        let daily_input = self.parse_input(reader)?;
+
+        parts.iter().map(|&part| daily_input.solve(part)).collect()
+    }
+
+    /// Run the requested parts of the solution over an in-memory input.
+    fn run_str(&self, input: &str, parts: &[Part]) -> Result<Vec<PartAnswer>, Error> {
+        self.run_reader(&mut input.as_bytes(), parts)
+    }
+
+    /// Run the requested parts of the solution over provided input source, parsing it only once.
+    fn run_parts(&self, source: &InputSource, parts: &[Part]) -> Result<Vec<PartAnswer>, Error> {
+        self.run_reader(&mut source.open()?, parts)
+    }
+
+    /// Run the solution over provided input path and return the answer of each part.
+    fn run_solution(&self, input_path: &Path) -> Result<Vec<PartAnswer>, Error> {
+        self.run_parts(&InputSource::File(input_path.to_path_buf()), &Part::ALL)
+    }
+
+    /// The input path used when none is provided.
+    fn default_input_path(&self) -> PathBuf {
+        input::inputs_directory().join(input::input_file_name(self.day_number()))
+    }
+
+    /// Run the requested parts of the solution over default input path.
+    fn run(&self, parts: &[Part]) -> Result<Vec<PartAnswer>, Error> {
+        let default_input_path = self.default_input_path();
+
+        log::info(
+            Some(self.day_number()),
+            format!("reading input '{}'", default_input_path.display()),
         );
-        Self::run_solution(default_input_path)
+        self.run_parts(&InputSource::File(default_input_path), parts)
+    }
+}
+
+/// The parsed input of a day, holding what is needed to solve any part of the puzzle.
+pub trait DailyInput {
+    /// Solve provided part of the puzzle. Parts do not depend on each other.
+    fn solve(&self, part: Part) -> Result<PartAnswer, Error>;
+
+    /// The number of records parsed from the input, such as measurements or bingo grids.
+    fn record_count(&self) -> usize;
+}
+
+#[cfg(test)]
+mod test_lib {
+    use super::{Answer, Part, PartAnswer};
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(42).to_string(), "42");
+        assert_eq!(Answer::Signed(-42).to_string(), "-42");
+        assert_eq!(Answer::from(String::from("ABC")).to_string(), "ABC");
+        assert_eq!(Answer::from((2, 37)).to_string(), "(2, 37)");
+    }
+
+    #[test]
+    fn test_part_answer_display() {
+        let part_answer = PartAnswer::new(Part::One, "Fuel cost", 37);
+        assert_eq!(part_answer.to_string(), "Fuel cost: 37");
+    }
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!("1".parse::<Part>(), Ok(Part::One));
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+        assert!("two".parse::<Part>().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "day_2")]
+    fn test_split_fields() {
+        use super::split_fields;
+
+        let fields: Vec<(usize, &str)> = split_fields(" 8  2 23", ' ').collect();
+        assert_eq!(fields, vec![(2, "8"), (5, "2"), (7, "23")]);
+
+        let fields: Vec<(usize, &str)> = split_fields("3,4, 12\n", ',').collect();
+        assert_eq!(fields, vec![(1, "3"), (3, "4"), (6, "12")]);
+    }
+
+    #[test]
+    #[cfg(feature = "tools")]
+    fn test_day_part_entries() {
+        use super::{day_part_entries, parse_day_part};
+        use crate::{Error, ParseErrorKind};
+
+        assert_eq!(parse_day_part(1, 1, "25:2"), Ok((25, Part::Two)));
+        for (key, column, text, kind) in [
+            ("7-1", 3, "7-1", ParseErrorKind::InvalidFormat),
+            ("x:1", 3, "x", ParseErrorKind::InvalidNumber),
+            ("26:1", 3, "26", ParseErrorKind::OutOfRange),
+            ("7:3", 5, "3", ParseErrorKind::OutOfRange),
+        ] {
+            let err = parse_day_part(4, 3, key).unwrap_err();
+            assert_eq!((err.day, err.line, err.column), (None, 4, column));
+            assert_eq!((err.text.as_str(), err.kind), (text, kind));
+        }
+
+        let entries: Vec<(usize, u8, Part, &str, usize)> =
+            day_part_entries("# day:part value\n\n 1:2   a b \n7:1")
+                .map(|entry| entry.unwrap())
+                .map(|entry| {
+                    (
+                        entry.line,
+                        entry.day,
+                        entry.part,
+                        entry.value,
+                        entry.value_column,
+                    )
+                })
+                .collect();
+        assert_eq!(
+            entries,
+            vec![(3, 1, Part::Two, "a b", 8), (4, 7, Part::One, "", 5)]
+        );
+        assert!(matches!(
+            day_part_entries("1:1 7\n0:1 5").nth(1),
+            Some(Err(Error::ParseError(_)))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "day_1")]
+    fn test_parse_number() {
+        use super::{expect_parse_error, parse_number};
+        use crate::ParseErrorKind;
+
+        assert_eq!(parse_number::<usize>(1, 2, 3, "42").unwrap(), 42);
+
+        let err = expect_parse_error(parse_number::<usize>(1, 2, 3, "4x2"));
+        assert_eq!((err.day, err.line, err.column), (Some(1), 2, 3));
+        assert_eq!(err.text, "4x2");
+        assert_eq!(err.kind, ParseErrorKind::InvalidNumber);
     }
 }
@@ -0,0 +1,210 @@
+//! This module is a logging layer reporting diagnostics of the runs, such as the input files,
+//! the parsed record counts, the duration of each phase and the warnings of the parsers.
+//! Messages are written to the standard error when their level is enabled, and can also be
+//! captured, whatever their level, to be written to a report file.
+
+use std::fmt::{Display, Formatter};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Mutex;
+
+use crate::Error;
+
+/// The importance of a log message, from the most to the least important.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    const ALL: [Level; 4] = [Level::Error, Level::Warn, Level::Info, Level::Debug];
+
+    /// The most verbose level to display, from the count of verbose and quiet flags.
+    /// Warnings are displayed by default, and nothing is displayed below errors.
+    pub fn from_verbosity(verbose: u8, quiet: u8) -> Option<Level> {
+        let index = (1 + verbose as isize - quiet as isize).min(Self::ALL.len() as isize - 1);
+
+        usize::try_from(index).ok().map(|index| Self::ALL[index])
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A logged message, about a day or about the whole run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LogRecord {
+    pub level: Level,
+    pub day: Option<u8>,
+    pub message: String,
+}
+
+impl Display for LogRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] ", self.level)?;
+        if let Some(day) = self.day {
+            write!(f, "day {}: ", day)?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// Dispatch log messages to the standard error and to the captured records.
+#[derive(Debug)]
+pub struct Logger {
+    /// The most verbose displayed level, as its position plus one, 0 displaying nothing.
+    max_level: AtomicU8,
+    capture: AtomicBool,
+    records: Mutex<Vec<LogRecord>>,
+}
+
+impl Logger {
+    /// A logger displaying warnings and errors, without capture.
+    pub const fn new() -> Self {
+        Self {
+            max_level: AtomicU8::new(Level::Warn as u8 + 1),
+            capture: AtomicBool::new(false),
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Set the most verbose displayed level, None displaying nothing, and whether every message
+    /// is captured.
+    pub fn configure(&self, max_level: Option<Level>, capture: bool) {
+        self.max_level.store(
+            max_level.map_or(0, |level| level as u8 + 1),
+            Ordering::Relaxed,
+        );
+        self.capture.store(capture, Ordering::Relaxed);
+    }
+
+    /// Whether messages of provided level are displayed.
+    pub fn is_displayed(&self, level: Level) -> bool {
+        (level as u8) < self.max_level.load(Ordering::Relaxed)
+    }
+
+    pub fn log(&self, level: Level, day: Option<u8>, message: String) {
+        let record = LogRecord {
+            level,
+            day,
+            message,
+        };
+
+        if self.is_displayed(level) {
+            eprintln!("{}", record);
+        }
+        if self.capture.load(Ordering::Relaxed) {
+            self.records.lock().unwrap().push(record);
+        }
+    }
+
+    /// Remove and return the captured records.
+    pub fn take_records(&self) -> Vec<LogRecord> {
+        std::mem::take(&mut *self.records.lock().unwrap())
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The logger used by the whole crate.
+static LOGGER: Logger = Logger::new();
+
+/// Configure the logger of the crate, see [`Logger::configure`].
+pub fn configure(max_level: Option<Level>, capture: bool) {
+    LOGGER.configure(max_level, capture);
+}
+
+pub fn error<S: Into<String>>(day: Option<u8>, message: S) {
+    LOGGER.log(Level::Error, day, message.into());
+}
+
+pub fn warn<S: Into<String>>(day: Option<u8>, message: S) {
+    LOGGER.log(Level::Warn, day, message.into());
+}
+
+pub fn info<S: Into<String>>(day: Option<u8>, message: S) {
+    LOGGER.log(Level::Info, day, message.into());
+}
+
+pub fn debug<S: Into<String>>(day: Option<u8>, message: S) {
+    LOGGER.log(Level::Debug, day, message.into());
+}
+
+/// Append the records captured so far to a report file, one record per line.
+pub fn write_report(path: &Path) -> Result<(), Error> {
+    let mut report = OpenOptions::new().create(true).append(true).open(path)?;
+
+    for record in LOGGER.take_records() {
+        writeln!(report, "{}", record)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_log {
+    use super::{Level, LogRecord, Logger};
+
+    #[test]
+    fn test_level_from_verbosity() {
+        assert_eq!(Level::from_verbosity(0, 0), Some(Level::Warn));
+        assert_eq!(Level::from_verbosity(1, 0), Some(Level::Info));
+        assert_eq!(Level::from_verbosity(5, 0), Some(Level::Debug));
+        assert_eq!(Level::from_verbosity(0, 1), Some(Level::Error));
+        assert_eq!(Level::from_verbosity(0, 2), None);
+        assert_eq!(Level::from_verbosity(1, 1), Some(Level::Warn));
+    }
+
+    #[test]
+    fn test_logger() {
+        let logger = Logger::new();
+        assert!(logger.is_displayed(Level::Warn));
+        assert!(!logger.is_displayed(Level::Info));
+
+        logger.log(Level::Warn, Some(2), String::from("not captured"));
+        assert!(logger.take_records().is_empty());
+
+        logger.configure(None, true);
+        assert!(!logger.is_displayed(Level::Error));
+        logger.log(
+            Level::Debug,
+            Some(2),
+            String::from("line 3 ignored: 'sideways 2'"),
+        );
+        logger.log(Level::Info, None, String::from("2 day(s) run"));
+
+        let records = logger.take_records();
+        assert_eq!(
+            records[0],
+            LogRecord {
+                level: Level::Debug,
+                day: Some(2),
+                message: String::from("line 3 ignored: 'sideways 2'")
+            }
+        );
+        assert_eq!(
+            records[0].to_string(),
+            "[DEBUG] day 2: line 3 ignored: 'sideways 2'"
+        );
+        assert_eq!(records[1].to_string(), "[INFO] 2 day(s) run");
+        assert!(logger.take_records().is_empty());
+    }
+}
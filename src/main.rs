@@ -1,31 +1,1198 @@
 #![deny(clippy::all)]
 
-use clap::Parser;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
 
-use aoc_2021_rs::{days, DailySolution};
+use clap::{ArgAction, Args, CommandFactory, ErrorKind, Parser, Subcommand};
+
+use aoc_2021_rs::bench::{bench_solution, Statistics};
+#[cfg(feature = "day_1")]
+use aoc_2021_rs::days::day_1::{self, ParseMode};
+#[cfg(feature = "day_2")]
+use aoc_2021_rs::days::day_2::{interpreter, parse_commands, TrajectoryFormat};
+#[cfg(feature = "day_1")]
+use aoc_2021_rs::depth_series::{analyze_report, report_table};
+use aoc_2021_rs::input::{is_valid_set_name, InputLocator, InputSource};
+use aoc_2021_rs::log::{self, Level};
+use aoc_2021_rs::memory::{format_bytes, profile_solution, CountingAllocator, MemoryUsage};
+use aoc_2021_rs::progress::{calendar, day_modules, Progress};
+use aoc_2021_rs::report::{self, summary_table, OutputFormat, RunRecord};
+use aoc_2021_rs::scaffold::scaffold_day;
+use aoc_2021_rs::submit::{
+    default_endpoint, submit_answer, Attempt, Endpoint, SubmissionHistory, SubmitOutcome,
+};
+use aoc_2021_rs::verify::{verify_answers, ExpectedAnswers, Verdict};
+use aoc_2021_rs::watch::{diff_records, AnswerChange, FileWatcher};
+use aoc_2021_rs::{cache, days, DailySolution, Error, Part};
+
+/// The allocator of the program, counting allocations only while memory profiling.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator::new();
 
 #[derive(Parser)]
-#[clap(author, version, about, arg_required_else_help = true)]
+#[clap(
+    author,
+    version,
+    about,
+    arg_required_else_help = true,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Cli {
-    /// Day solution(s) to run with provided inputs in folder aoc_inputs.
-    #[clap(short, long, min_values = 1, required = true)]
-    running_day: Vec<u8>,
+    #[clap(flatten)]
+    selection: DaySelection,
+
+    /// Output format of the answers: text, json or csv.
+    #[clap(short, long, value_parser, default_value = "text")]
+    output: OutputFormat,
+
+    /// Number of days run concurrently.
+    #[clap(short, long, value_parser, default_value_t = 1)]
+    jobs: usize,
+
+    /// Report the memory allocated by input parsing and by each part, running days one by one.
+    #[clap(long, conflicts_with_all = &["output", "jobs"])]
+    profile_memory: bool,
+
+    /// Display more diagnostics on the standard error, repeated for even more.
+    #[clap(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Display less diagnostics on the standard error, repeated to hide errors too.
+    #[clap(short, long, global = true, action = ArgAction::Count)]
+    quiet: u8,
+
+    /// Write every diagnostic of the run to provided report file.
+    #[clap(long, global = true, value_parser)]
+    log_file: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+/// The days and parts to use.
+#[derive(Args)]
+struct DaySelection {
+    /// Day solution(s) to run with provided inputs in folder aoc_inputs, as numbers or ranges such as 1-7.
+    #[clap(
+        short,
+        long,
+        min_values = 1,
+        required_unless_present = "all",
+        value_parser = parse_day_range
+    )]
+    running_day: Vec<RangeInclusive<u8>>,
+
+    /// Select every implemented day.
+    #[clap(short, long)]
+    all: bool,
+
+    /// Puzzle part to run, both parts are run by default.
+    #[clap(short, long, value_parser)]
+    part: Option<Part>,
+
+    /// Input file of a day as <DAY>=<PATH>, '-' as path reads the standard input.
+    #[clap(short, long = "input", value_parser = parse_day_input)]
+    inputs: Vec<(u8, InputSource)>,
+
+    /// Handling of bad day 1 readings: strict fails, skip ignores them, interpolate replaces them.
+    #[cfg(feature = "day_1")]
+    #[clap(long, value_parser, default_value = "strict")]
+    bad_readings: ParseMode,
+
+    #[clap(flatten)]
+    location: InputsLocation,
+}
+
+/// Where the daily input files are stored.
+#[derive(Args)]
+struct InputsLocation {
+    /// Directory of the daily input files, instead of $AOC_INPUTS_DIR or ./aoc_inputs.
+    #[clap(long, value_parser)]
+    input_dir: Option<PathBuf>,
+
+    /// Named input set to use, stored as a subdirectory of the inputs directory.
+    #[clap(long, value_parser = parse_input_set)]
+    input_set: Option<String>,
+}
+
+impl InputsLocation {
+    fn locator(&self) -> InputLocator {
+        let mut locator = InputLocator::new();
+        if let Some(input_dir) = &self.input_dir {
+            locator = locator.with_directory(input_dir.clone());
+        }
+        if let Some(input_set) = &self.input_set {
+            locator = locator.with_set(input_set.clone());
+        }
+
+        locator
+    }
+}
+
+fn parse_day_range(value: &str) -> Result<RangeInclusive<u8>, String> {
+    let parse_day = |day: &str| {
+        day.trim()
+            .parse::<u8>()
+            .map_err(|_| format!("invalid day number '{}'", day))
+    };
+
+    match value.split_once('-') {
+        None => parse_day(value).map(|day| day..=day),
+        Some((first, last)) => {
+            let (first, last) = (parse_day(first)?, parse_day(last)?);
+            if first > last {
+                return Err(format!("empty day range '{}'", value));
+            }
+            Ok(first..=last)
+        }
+    }
+}
+
+fn parse_input_set(value: &str) -> Result<String, String> {
+    if is_valid_set_name(value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!("invalid input set name '{}'", value))
+    }
+}
+
+fn parse_day_part(value: &str) -> Result<(u8, Part), String> {
+    aoc_2021_rs::parse_day_part(1, 1, value)
+        .map_err(|err| format!("{} '{}', expected <DAY>:<PART>", err.kind, err.text))
+}
+
+fn parse_day_input(value: &str) -> Result<(u8, InputSource), String> {
+    let (day, source) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected <DAY>=<PATH>, found '{}'", value))?;
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| format!("invalid day number '{}'", day))?;
+
+    Ok((day, source.parse()?))
+}
+
+impl DaySelection {
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+
+    fn locator(&self) -> InputLocator {
+        let stdin_count = self
+            .inputs
+            .iter()
+            .filter(|(_, source)| *source == InputSource::Stdin)
+            .count();
+        if stdin_count > 1 {
+            Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    "the standard input can be used by a single day",
+                )
+                .exit();
+        }
+
+        let mut locator = self.location.locator();
+        for (day, source) in self.inputs.iter() {
+            locator = locator.with_day_source(*day, source.clone());
+        }
+
+        locator
+    }
+
+    /// The selected day numbers, in requested order and without duplicates.
+    fn day_numbers(&self) -> Vec<u8> {
+        if self.all {
+            return days::SOLUTIONS
+                .iter()
+                .map(|solution| solution.day_number())
+                .collect();
+        }
+
+        let mut day_numbers = Vec::new();
+        for day_number in self.running_day.iter().cloned().flatten() {
+            if !day_numbers.contains(&day_number) {
+                day_numbers.push(day_number);
+            }
+        }
+
+        day_numbers
+    }
+
+    /// The selected day numbers without solution.
+    fn missing_days(&self) -> Vec<u8> {
+        self.day_numbers()
+            .into_iter()
+            .filter(|&day_number| days::get_solution(day_number).is_none())
+            .collect()
+    }
+
+    /// The solutions of the selected days, reporting the days which are not implemented.
+    fn solutions(&self) -> Vec<&'static dyn DailySolution> {
+        self.day_numbers()
+            .into_iter()
+            .filter_map(|day_number| {
+                let solution = days::get_solution(day_number);
+                #[cfg(feature = "day_1")]
+                let solution = solution.map(|solution| match day_number {
+                    day_1::Solution::DAY_NUMBER => day_1::solution(self.bad_readings),
+                    _ => solution,
+                });
+                if solution.is_none() {
+                    log::error(Some(day_number), "not implemented");
+                }
+                solution
+            })
+            .collect()
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the implemented day solutions.
+    List,
+
+    /// Measure the duration of input parsing and of each part of the selected days.
+    Bench {
+        #[clap(flatten)]
+        selection: DaySelection,
+
+        /// Number of measured runs for each day.
+        #[clap(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+    },
+
+    /// Check the answers of the selected days against the recorded expected answers.
+    Verify {
+        #[clap(flatten)]
+        selection: DaySelection,
+
+        /// File of expected answers, expected_answers of the inputs directory by default.
+        #[clap(short, long, value_parser)]
+        expected: Option<PathBuf>,
+    },
+
+    /// Run the selected days again each time one of their input files changes.
+    Watch {
+        #[clap(flatten)]
+        selection: DaySelection,
+
+        /// Alternate input file of a day as <DAY>=<PATH>, watched and run along the day input.
+        #[clap(long = "alt-input", value_parser = parse_day_input)]
+        alt_inputs: Vec<(u8, InputSource)>,
+
+        /// Delay between two polls of the input files, in milliseconds.
+        #[clap(long, value_parser, default_value_t = 500)]
+        interval: u64,
+    },
+
+    /// Generate and register the skeleton of a new day solution, with an empty input file.
+    NewDay {
+        /// Number of the new day.
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Title of the puzzle.
+        #[clap(short, long)]
+        title: Option<String>,
+
+        /// Root directory of the project, holding the src directory.
+        #[clap(long, value_parser, default_value = ".")]
+        project_dir: PathBuf,
+
+        #[clap(flatten)]
+        location: InputsLocation,
+    },
+
+    /// Submit the answer of a day part, unless it is already known to be wrong.
+    Submit {
+        /// Number of the day.
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Puzzle part of the answer.
+        #[clap(value_parser)]
+        part: Part,
+
+        /// Answer to submit, computed by the day solution over its input by default.
+        #[clap(short, long)]
+        answer: Option<String>,
+
+        /// Answer endpoint, instead of $AOC_SUBMIT_URL or the local mock server.
+        #[clap(long, value_parser)]
+        endpoint: Option<Endpoint>,
+
+        /// File of submitted answers, submissions of the inputs directory by default.
+        #[clap(long, value_parser)]
+        history: Option<PathBuf>,
+
+        #[clap(flatten)]
+        location: InputsLocation,
+    },
+
+    /// Print statistics of sliding windows over the depth measurements of day 1.
+    #[cfg(feature = "day_1")]
+    DepthReport {
+        /// Number of measurements summed by each window, repeated for several window sizes.
+        #[clap(
+            short,
+            long = "window",
+            value_parser = clap::value_parser!(u64).range(1..),
+            default_values = &["1", "3"]
+        )]
+        windows: Vec<u64>,
+
+        /// Depth measurements to analyse, '-' reads the standard input, the day 1 input by default.
+        #[clap(short, long, value_parser)]
+        input: Option<InputSource>,
+
+        /// Handling of bad readings: strict fails, skip ignores them, interpolate replaces them.
+        #[clap(long, value_parser, default_value = "strict")]
+        bad_readings: ParseMode,
+
+        #[clap(flatten)]
+        location: InputsLocation,
+    },
+
+    /// Export the trajectory followed by the submarine over a day 2 course, step by step.
+    #[cfg(feature = "day_2")]
+    Trajectory {
+        /// Puzzle part whose movement model is used.
+        #[clap(short, long, value_parser, default_value = "2")]
+        part: Part,
+
+        /// Format of the trajectory: csv or svg.
+        #[clap(short, long, value_parser, default_value = "csv")]
+        format: TrajectoryFormat,
+
+        /// File to write the trajectory to, instead of the standard output.
+        #[clap(short, long, value_parser)]
+        output: Option<PathBuf>,
+
+        /// Course to follow, '-' reads the standard input, the day 2 input by default.
+        #[clap(short, long, value_parser)]
+        input: Option<InputSource>,
+
+        #[clap(flatten)]
+        location: InputsLocation,
+    },
+
+    /// Show the event calendar with the implemented, verified and starred parts of each day.
+    Status {
+        /// Record a star earned for a day part as <DAY>:<PART>, before showing the calendar.
+        #[clap(long = "star", value_parser = parse_day_part)]
+        stars: Vec<(u8, Part)>,
+
+        /// Root directory of the project, holding the src directory.
+        #[clap(long, value_parser, default_value = ".")]
+        project_dir: PathBuf,
+
+        #[clap(flatten)]
+        location: InputsLocation,
+    },
+
+    /// Manage the daily input files.
+    Inputs {
+        #[clap(subcommand)]
+        command: InputsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum InputsCommand {
+    /// List the input files of the implemented days, and the other ones found.
+    List {
+        #[clap(flatten)]
+        location: InputsLocation,
+    },
+
+    /// Check that the input files of the selected days are accepted by their parser.
+    Validate {
+        #[clap(flatten)]
+        selection: DaySelection,
+    },
+
+    /// Copy a file as the input of a day, after checking it with the parser of the day.
+    Import {
+        /// Number of the day.
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// File to import.
+        #[clap(value_parser)]
+        file: PathBuf,
+
+        /// Replace an existing input file.
+        #[clap(short, long)]
+        force: bool,
+
+        /// Import the file even if it cannot be parsed.
+        #[clap(long)]
+        no_validate: bool,
+
+        #[clap(flatten)]
+        location: InputsLocation,
+    },
 }
 
 fn main() {
     let cli: Cli = Cli::parse();
 
-    for requested_day_number in cli.running_day.into_iter() {
-        match requested_day_number {
-            days::day_1::Solution::DAY_NUMBER => days::day_1::Solution::run(),
-            days::day_2::Solution::DAY_NUMBER => days::day_2::Solution::run(),
-            days::day_3::Solution::DAY_NUMBER => days::day_3::Solution::run(),
-            days::day_4::Solution::DAY_NUMBER => days::day_4::Solution::run(),
-            days::day_5::Solution::DAY_NUMBER => days::day_5::Solution::run(),
-            days::day_6::Solution::DAY_NUMBER => days::day_6::Solution::run(),
-            days::day_7::Solution::DAY_NUMBER => days::day_7::Solution::run(),
-            _ => panic!("the day {} is not implemented", requested_day_number),
-        }
-        .unwrap();
+    log::configure(
+        Level::from_verbosity(cli.verbose, cli.quiet),
+        cli.log_file.is_some(),
+    );
+    if let Some(log_file) = &cli.log_file {
+        if let Err(err) = fs::File::create(log_file) {
+            eprintln!("Cannot create log file '{}': {}", log_file.display(), err);
+            std::process::exit(1);
+        }
+    }
+
+    let has_failure = match cli.command {
+        None if cli.profile_memory => profile_days(&cli.selection),
+        None => run_days(&cli.selection, cli.output, cli.jobs),
+        Some(Command::List) => {
+            list_days();
+            false
+        }
+        Some(Command::Bench {
+            selection,
+            iterations,
+        }) => bench_days(&selection, iterations),
+        Some(Command::Verify {
+            selection,
+            expected,
+        }) => verify_days(&selection, expected),
+        Some(Command::Watch {
+            selection,
+            alt_inputs,
+            interval,
+        }) => watch_days(
+            &selection,
+            &alt_inputs,
+            Duration::from_millis(interval),
+            cli.log_file.as_deref(),
+        ),
+        Some(Command::NewDay {
+            day,
+            title,
+            project_dir,
+            location,
+        }) => new_day(day, title, &project_dir, &location),
+        Some(Command::Submit {
+            day,
+            part,
+            answer,
+            endpoint,
+            history,
+            location,
+        }) => submit_day(day, part, answer, endpoint, history, &location),
+        #[cfg(feature = "day_1")]
+        Some(Command::DepthReport {
+            windows,
+            input,
+            bad_readings,
+            location,
+        }) => print_depth_report(&windows, input, bad_readings, &location),
+        #[cfg(feature = "day_2")]
+        Some(Command::Trajectory {
+            part,
+            format,
+            output,
+            input,
+            location,
+        }) => export_trajectory(part, format, output.as_deref(), input, &location),
+        Some(Command::Status {
+            stars,
+            project_dir,
+            location,
+        }) => show_status(&stars, &project_dir, &location),
+        Some(Command::Inputs { command }) => manage_inputs(command),
+    };
+
+    if let Some(log_file) = &cli.log_file {
+        write_log_file(log_file);
+    }
+    if has_failure {
+        std::process::exit(1);
+    }
+}
+
+/// Append the captured diagnostics to the log file, reporting a failure on the standard error.
+fn write_log_file(log_file: &Path) {
+    if let Err(err) = log::write_report(log_file) {
+        eprintln!("Cannot write log file '{}': {}", log_file.display(), err);
+    }
+}
+
+fn list_days() {
+    for solution in days::SOLUTIONS {
+        println!(
+            "Day {:>2}: {} ({})",
+            solution.day_number(),
+            solution.title(),
+            solution.url()
+        );
+    }
+}
+
+/// Run the selected days, returning whether a day failed.
+fn run_days(selection: &DaySelection, output: OutputFormat, jobs: usize) -> bool {
+    let parts = selection.parts();
+    let locator = selection.locator();
+    let days: Vec<(&dyn DailySolution, InputSource)> = selection
+        .solutions()
+        .into_iter()
+        .map(|solution| (solution, locator.locate(solution.day_number())))
+        .collect();
+    let mut records: Vec<RunRecord> = Vec::new();
+
+    log::debug(
+        None,
+        format!("running {} day(s) over {} job(s)", days.len(), jobs),
+    );
+    report::run_days(&days, &parts, jobs, |solution, day_records| {
+        if output == OutputFormat::Text {
+            println!("Day {}: {}", solution.day_number(), solution.title());
+            print!("{}", output.render(&day_records));
+        }
+        records.extend(day_records);
+    });
+
+    if output == OutputFormat::Text {
+        println!();
+        print!("{}", summary_table(&records, &selection.missing_days()));
+    } else {
+        print!("{}", output.render(&records));
+    }
+
+    records.iter().any(|record| record.outcome.is_err())
+}
+
+/// Benchmark the selected days, returning whether a day failed.
+fn bench_days(selection: &DaySelection, iterations: usize) -> bool {
+    let mut has_failure = false;
+    let parts = selection.parts();
+    let locator = selection.locator();
+
+    for solution in selection.solutions() {
+        let source = locator.locate(solution.day_number());
+
+        match bench_solution(solution, &source, &parts, iterations) {
+            Ok(report) => {
+                println!(
+                    "Day {} over {} iteration(s):",
+                    report.day, report.iterations
+                );
+                print_statistics("parsing", &report.parsing);
+                for (part, statistics) in report.parts.iter() {
+                    print_statistics(&format!("part {}", part), statistics);
+                }
+            }
+            Err(err) => {
+                log::error(Some(solution.day_number()), format!("failed: {}", err));
+                has_failure = true;
+            }
+        }
+    }
+
+    has_failure
+}
+
+fn print_statistics(phase: &str, statistics: &Statistics) {
+    println!(
+        "    {:<8} min {:>12?}  median {:>12?}  mean {:>12?}",
+        phase, statistics.min, statistics.median, statistics.mean
+    );
+}
+
+/// Measure the memory allocated by the selected days, returning whether a day failed.
+/// Allocations are only counted from here, so that other runs do not pay for counting.
+fn profile_days(selection: &DaySelection) -> bool {
+    ALLOCATOR.set_enabled(true);
+    let mut has_failure = false;
+    let parts = selection.parts();
+    let locator = selection.locator();
+
+    for solution in selection.solutions() {
+        let source = locator.locate(solution.day_number());
+
+        match profile_solution(&ALLOCATOR, solution, &source, &parts) {
+            Ok(report) => {
+                println!("Day {} memory usage:", report.day);
+                print_memory_usage("parsing", &report.parsing);
+                for (part, usage) in report.parts.iter() {
+                    print_memory_usage(&format!("part {}", part), usage);
+                }
+            }
+            Err(err) => {
+                log::error(Some(solution.day_number()), format!("failed: {}", err));
+                has_failure = true;
+            }
+        }
+    }
+
+    has_failure
+}
+
+fn print_memory_usage(phase: &str, usage: &MemoryUsage) {
+    println!(
+        "    {:<8} peak {:>10}  total {:>10}  allocations {:>8}",
+        phase,
+        format_bytes(usage.peak),
+        format_bytes(usage.total),
+        usage.allocations
+    );
+}
+
+/// Verify the answers of the selected days, returning whether an answer is wrong or a day failed.
+fn verify_days(selection: &DaySelection, expected_path: Option<PathBuf>) -> bool {
+    let locator = selection.locator();
+    let expected_path =
+        expected_path.unwrap_or_else(|| ExpectedAnswers::default_path(&locator.directory()));
+    let expected_answers = match ExpectedAnswers::load(&expected_path) {
+        Ok(expected_answers) => expected_answers,
+        Err(err) => {
+            eprintln!(
+                "Cannot load expected answers '{}': {}",
+                expected_path.display(),
+                err
+            );
+            return true;
+        }
+    };
+
+    let mut has_failure = false;
+    let parts = selection.parts();
+    let mut verified_parts = Vec::new();
+
+    for solution in selection.solutions() {
+        let source = locator.locate(solution.day_number());
+        let part_answers = match solution.run_parts(&source, &parts) {
+            Ok(part_answers) => part_answers,
+            Err(err) => {
+                log::error(Some(solution.day_number()), format!("failed: {}", err));
+                has_failure = true;
+                continue;
+            }
+        };
+
+        for verification in verify_answers(&expected_answers, solution.day_number(), part_answers) {
+            let status = match &verification.verdict {
+                Verdict::Pass => String::from("PASS"),
+                Verdict::Fail { expected } => format!("FAIL (expected {})", expected),
+                Verdict::Missing => String::from("MISSING"),
+            };
+            let part = verification.answer.part;
+            println!(
+                "Day {} part {}: {} {}",
+                verification.day, part, verification.answer, status
+            );
+            has_failure |= verification.is_regression();
+            match verification.verdict {
+                Verdict::Pass => verified_parts.push((verification.day, part, true)),
+                Verdict::Fail { .. } => verified_parts.push((verification.day, part, false)),
+                Verdict::Missing => {}
+            }
+        }
+    }
+
+    update_progress(&locator.directory(), |progress| {
+        for (day, part, verified) in verified_parts {
+            progress.set_verified(day, part, verified);
+        }
+    });
+
+    has_failure
+}
+
+/// Scaffold a new day, returning whether it failed.
+fn new_day(day: u8, title: Option<String>, project_dir: &Path, location: &InputsLocation) -> bool {
+    let title = title.unwrap_or_else(|| format!("Day {}", day));
+    let inputs_directory = location.locator().directory();
+
+    match scaffold_day(project_dir, &inputs_directory, day, &title) {
+        Ok(scaffold) => {
+            println!("Created day module '{}'", scaffold.module_path.display());
+            println!(
+                "Registered day {} into '{}' and its feature into '{}'",
+                day,
+                scaffold.registry_path.display(),
+                scaffold.manifest_path.display()
+            );
+            match scaffold.input_path {
+                Some(input_path) => println!("Created input file '{}'", input_path.display()),
+                None => println!("Kept existing input file of day {}", day),
+            }
+            false
+        }
+        Err(err) => {
+            eprintln!("Cannot scaffold day {}: {}", day, err);
+            true
+        }
+    }
+}
+
+/// Submit the answer of a day part, returning whether it is not known as correct.
+fn submit_day(
+    day: u8,
+    part: Part,
+    answer: Option<String>,
+    endpoint: Option<Endpoint>,
+    history_path: Option<PathBuf>,
+    location: &InputsLocation,
+) -> bool {
+    let locator = location.locator();
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let part_answer = days::get_solution(day)
+                .ok_or_else(|| format!("day {} is not implemented", day))
+                .and_then(|solution| {
+                    solution
+                        .run_part(&locator.locate(day), part)
+                        .map_err(|err| err.to_string())
+                });
+            match part_answer {
+                Ok(part_answer) => part_answer.answer.to_string(),
+                Err(err) => {
+                    eprintln!(
+                        "Cannot compute the answer of day {} part {}: {}",
+                        day, part, err
+                    );
+                    return true;
+                }
+            }
+        }
+    };
+    let endpoint = match endpoint.map_or_else(|| default_endpoint().parse(), Ok) {
+        Ok(endpoint) => endpoint,
+        Err(err) => {
+            eprintln!("Invalid endpoint: {}", err);
+            return true;
+        }
+    };
+    let history_path =
+        history_path.unwrap_or_else(|| SubmissionHistory::default_path(&locator.directory()));
+    let mut history = match SubmissionHistory::load(&history_path) {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!(
+                "Cannot load submissions '{}': {}",
+                history_path.display(),
+                err
+            );
+            return true;
+        }
+    };
+
+    if let Some(outcome) = history.known_outcome(day, part, &answer) {
+        println!(
+            "Day {} part {}: answer {} not submitted, already known as {}",
+            day, part, answer, outcome
+        );
+        return outcome != SubmitOutcome::Correct;
+    }
+
+    let outcome = match submit_answer(&endpoint, day, part, &answer) {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            eprintln!("Cannot submit day {} part {}: {}", day, part, err);
+            return true;
+        }
+    };
+    println!(
+        "Day {} part {}: answer {} is {}",
+        day, part, answer, outcome
+    );
+
+    let is_correct = matches!(
+        outcome,
+        SubmitOutcome::Correct | SubmitOutcome::AlreadySolved
+    );
+    if is_correct {
+        update_progress(&locator.directory(), |progress| {
+            progress.set_star(day, part)
+        });
+    }
+    let attempt = Attempt {
+        day,
+        part,
+        outcome,
+        answer,
+    };
+    if let Err(err) = history.record(&history_path, attempt) {
+        eprintln!(
+            "Cannot record submission '{}': {}",
+            history_path.display(),
+            err
+        );
+    }
+
+    !is_correct
+}
+
+/// Print the statistics of the depth measurements of day 1, returning whether they cannot be read.
+/// Measurements are read in a single pass, without storing them, and tolerated bad readings are
+/// logged as warnings.
+#[cfg(feature = "day_1")]
+fn print_depth_report(
+    windows: &[u64],
+    input: Option<InputSource>,
+    mode: ParseMode,
+    location: &InputsLocation,
+) -> bool {
+    let source = input.unwrap_or_else(|| location.locator().locate(1));
+    let windows: Vec<usize> = windows.iter().map(|&window| window as usize).collect();
+
+    match source
+        .open()
+        .and_then(|reader| analyze_report(reader, &windows, mode))
+    {
+        Ok((statistics, anomalies)) => {
+            println!(
+                "Depth measurements '{}', {} bad reading(s):",
+                source,
+                anomalies.len()
+            );
+            print!("{}", report_table(&statistics));
+            false
+        }
+        Err(err) => {
+            eprintln!("Cannot analyse depths '{}': {}", source, err);
+            true
+        }
+    }
+}
+
+/// Export the trajectory of the course of day 2, returning whether it failed.
+#[cfg(feature = "day_2")]
+fn export_trajectory(
+    part: Part,
+    format: TrajectoryFormat,
+    output: Option<&Path>,
+    input: Option<InputSource>,
+    location: &InputsLocation,
+) -> bool {
+    let source = input.unwrap_or_else(|| location.locator().locate(2));
+    let commands = match source
+        .read_to_string()
+        .and_then(|content| parse_commands(&content))
+    {
+        Ok(commands) => commands,
+        Err(err) => {
+            eprintln!("Cannot read course '{}': {}", source, err);
+            return true;
+        }
+    };
+
+    let trajectory = match interpreter(part).trace(&commands) {
+        Ok(steps) => format.render(&steps),
+        Err(err) => {
+            eprintln!("Cannot follow course '{}': {}", source, err);
+            return true;
+        }
+    };
+    match output {
+        None => {
+            print!("{}", trajectory);
+            false
+        }
+        Some(output) => match fs::write(output, trajectory) {
+            Ok(()) => {
+                println!(
+                    "Wrote the trajectory of {} command(s) to '{}'",
+                    commands.len(),
+                    output.display()
+                );
+                false
+            }
+            Err(err) => {
+                eprintln!("Cannot write '{}': {}", output.display(), err);
+                true
+            }
+        },
+    }
+}
+
+/// Apply changes to the progress file of provided inputs directory, reporting failures on the
+/// standard error.
+fn update_progress<F>(inputs_directory: &Path, update: F)
+where
+    F: FnOnce(&mut Progress),
+{
+    let progress_path = Progress::default_path(inputs_directory);
+    let result = Progress::load(&progress_path).and_then(|mut progress| {
+        update(&mut progress);
+        progress.save(&progress_path)
+    });
+
+    if let Err(err) = result {
+        eprintln!(
+            "Cannot update progress '{}': {}",
+            progress_path.display(),
+            err
+        );
+    }
+}
+
+/// Record the provided stars and show the event calendar, returning whether it failed.
+/// Implemented days are the day modules of the project, or the registered days when the project
+/// sources are not available.
+fn show_status(stars: &[(u8, Part)], project_dir: &Path, location: &InputsLocation) -> bool {
+    let inputs_directory = location.locator().directory();
+    if !stars.is_empty() {
+        update_progress(&inputs_directory, |progress| {
+            for &(day, part) in stars {
+                progress.set_star(day, part);
+            }
+        });
+    }
+
+    let progress_path = Progress::default_path(&inputs_directory);
+    let progress = match Progress::load(&progress_path) {
+        Ok(progress) => progress,
+        Err(err) => {
+            eprintln!(
+                "Cannot load progress '{}': {}",
+                progress_path.display(),
+                err
+            );
+            return true;
+        }
+    };
+
+    let days_directory = project_dir.join("src").join("days");
+    let implemented_days = if days_directory.is_dir() {
+        match day_modules(&days_directory) {
+            Ok(implemented_days) => implemented_days,
+            Err(err) => {
+                eprintln!(
+                    "Cannot list day modules of '{}': {}",
+                    days_directory.display(),
+                    err
+                );
+                return true;
+            }
+        }
+    } else {
+        days::SOLUTIONS
+            .iter()
+            .map(|solution| solution.day_number())
+            .collect()
+    };
+
+    print!("{}", calendar(&progress, &implemented_days));
+
+    false
+}
+
+/// Run an inputs subcommand, returning whether it failed.
+fn manage_inputs(command: InputsCommand) -> bool {
+    match command {
+        InputsCommand::List { location } => list_inputs(&location),
+        InputsCommand::Validate { selection } => validate_inputs(&selection),
+        InputsCommand::Import {
+            day,
+            file,
+            force,
+            no_validate,
+            location,
+        } => import_input(day, &file, force, no_validate, &location),
+    }
+}
+
+/// List the input files of the selected location, returning whether they cannot be listed.
+fn list_inputs(location: &InputsLocation) -> bool {
+    let locator = location.locator();
+    let directory = locator.directory();
+    let implemented_days = days::SOLUTIONS.iter().map(|solution| solution.day_number());
+
+    let entries = match cache::list_inputs(&directory, implemented_days) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("Cannot list inputs of '{}': {}", directory.display(), err);
+            return true;
+        }
+    };
+
+    println!("Inputs directory '{}':", directory.display());
+    for entry in entries {
+        let size = match entry.size {
+            Some(size) => format!("{} bytes", size),
+            None => String::from("missing"),
+        };
+        let implemented = match days::get_solution(entry.day) {
+            Some(_) => "",
+            None => " (not implemented)",
+        };
+        println!("Day {:>2}: {}{}", entry.day, size, implemented);
+    }
+
+    if location.input_set.is_none() {
+        if let Ok(sets) = cache::input_sets(&directory) {
+            if !sets.is_empty() {
+                println!("Input sets: {}", sets.join(", "));
+            }
+        }
+    }
+
+    false
+}
+
+/// Parse the inputs of the selected days, returning whether one of them is invalid.
+fn validate_inputs(selection: &DaySelection) -> bool {
+    let locator = selection.locator();
+    let mut has_failure = false;
+
+    for solution in selection.solutions() {
+        let source = locator.locate(solution.day_number());
+        match solution.parse_source(&source) {
+            Ok(_) => println!("Day {} input '{}': valid", solution.day_number(), source),
+            Err(Error::IOError(err)) if err.kind() == io::ErrorKind::NotFound => {
+                println!("Day {} input '{}': missing", solution.day_number(), source);
+                has_failure = true;
+            }
+            Err(err) => {
+                println!(
+                    "Day {} input '{}': invalid, {}",
+                    solution.day_number(),
+                    source,
+                    err
+                );
+                has_failure = true;
+            }
+        }
+    }
+
+    has_failure
+}
+
+/// Import a file as the input of a day, returning whether it failed.
+fn import_input(
+    day: u8,
+    file: &Path,
+    force: bool,
+    no_validate: bool,
+    location: &InputsLocation,
+) -> bool {
+    if let Some(solution) = days::get_solution(day) {
+        if let Err(err) = solution.parse_source(&InputSource::File(file.to_path_buf())) {
+            if !no_validate {
+                eprintln!(
+                    "Cannot import '{}' for day {}: {}",
+                    file.display(),
+                    day,
+                    err
+                );
+                return true;
+            }
+            eprintln!("Importing '{}' despite: {}", file.display(), err);
+        }
+    }
+
+    match cache::import_input(file, &location.locator().directory(), day, force) {
+        Ok(path) => {
+            println!("Imported '{}' as '{}'", file.display(), path.display());
+            false
+        }
+        Err(err) => {
+            eprintln!(
+                "Cannot import '{}' for day {}: {}",
+                file.display(),
+                day,
+                err
+            );
+            true
+        }
+    }
+}
+
+/// Watch the input files of the selected days, running a day again when one of its inputs changes.
+fn watch_days(
+    selection: &DaySelection,
+    alt_inputs: &[(u8, InputSource)],
+    interval: Duration,
+    log_file: Option<&Path>,
+) -> bool {
+    let parts = selection.parts();
+    let locator = selection.locator();
+    let mut targets: Vec<(&dyn DailySolution, PathBuf)> = Vec::new();
+
+    for solution in selection.solutions() {
+        let day_sources = std::iter::once(locator.locate(solution.day_number())).chain(
+            alt_inputs
+                .iter()
+                .filter(|(day, _)| *day == solution.day_number())
+                .map(|(_, source)| source.clone()),
+        );
+
+        for source in day_sources {
+            match source {
+                InputSource::File(path) => targets.push((solution, path)),
+                InputSource::Stdin => Cli::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        "the standard input cannot be watched",
+                    )
+                    .exit(),
+            }
+        }
+    }
+
+    let mut watcher = FileWatcher::new(targets.iter().map(|(_, path)| path.clone()));
+    let mut previous_records: HashMap<(u8, PathBuf), Vec<RunRecord>> = HashMap::new();
+    let mut changed_paths: Vec<PathBuf> = targets.iter().map(|(_, path)| path.clone()).collect();
+
+    println!(
+        "Watching {} input file(s), press Ctrl+C to stop",
+        targets.len()
+    );
+    loop {
+        for (solution, path) in targets
+            .iter()
+            .filter(|(_, path)| changed_paths.contains(path))
+        {
+            let records = report::run_day(*solution, &InputSource::File(path.clone()), &parts);
+            let key = (solution.day_number(), path.clone());
+
+            println!(
+                "Day {} over input file '{}':",
+                solution.day_number(),
+                path.display()
+            );
+            for (record, change) in diff_records(
+                previous_records.get(&key).map_or(&[], Vec::as_slice),
+                &records,
+            ) {
+                let change = match change {
+                    AnswerChange::New => String::new(),
+                    AnswerChange::Unchanged => String::from(" (unchanged)"),
+                    AnswerChange::Changed { previous } => format!(" (was {})", previous),
+                };
+                match &record.outcome {
+                    Ok(part_answer) => println!("    {}{}", part_answer, change),
+                    Err(err) => println!("    Part {} failed: {}{}", record.part, err, change),
+                }
+            }
+
+            previous_records.insert(key, records);
+        }
+        if let Some(log_file) = log_file {
+            write_log_file(log_file);
+        }
+
+        thread::sleep(interval);
+        changed_paths = watcher.poll();
     }
 }
@@ -0,0 +1,230 @@
+//! This module measures the memory allocated by daily solutions.
+//! Measures rely on a [`CountingAllocator`], which must be registered as the global allocator of
+//! the program. As counters are shared by every thread, measures are only accurate when a single
+//! solution runs at a time. Counting is disabled by default, so that the allocator costs a single
+//! atomic load per allocation outside of memory profiling.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+
+use crate::input::InputSource;
+use crate::{DailySolution, Error, Part};
+
+/// An allocator delegating to the system allocator, which counts allocated bytes.
+#[derive(Debug, Default)]
+pub struct CountingAllocator {
+    enabled: AtomicBool,
+    /// Bytes currently allocated since counting is enabled, which is negative when more bytes
+    /// allocated before are freed.
+    current: AtomicIsize,
+    /// Highest value of `current` since the last reset.
+    peak: AtomicIsize,
+    /// Bytes allocated since the program start, freed or not.
+    total: AtomicUsize,
+    allocations: AtomicUsize,
+}
+
+impl CountingAllocator {
+    pub const fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            current: AtomicIsize::new(0),
+            peak: AtomicIsize::new(0),
+            total: AtomicUsize::new(0),
+            allocations: AtomicUsize::new(0),
+        }
+    }
+
+    /// Enable or disable counting, allocations being only counted while it is enabled.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    fn record_allocation(&self, size: usize) {
+        let size = size as isize;
+        let current = self.current.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+        self.total.fetch_add(size as usize, Ordering::Relaxed);
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_deallocation(&self, size: usize) {
+        self.current.fetch_sub(size as isize, Ordering::Relaxed);
+    }
+
+    /// Run provided closure and measure the memory it allocates, which is only counted while
+    /// counting is enabled.
+    pub fn measure<T, F>(&self, f: F) -> (T, MemoryUsage)
+    where
+        F: FnOnce() -> T,
+    {
+        let baseline = self.current.load(Ordering::Relaxed);
+        self.peak.store(baseline, Ordering::Relaxed);
+        let total = self.total.load(Ordering::Relaxed);
+        let allocations = self.allocations.load(Ordering::Relaxed);
+
+        let result = f();
+
+        let usage = MemoryUsage {
+            peak: (self.peak.load(Ordering::Relaxed) - baseline).max(0) as usize,
+            total: self.total.load(Ordering::Relaxed) - total,
+            allocations: self.allocations.load(Ordering::Relaxed) - allocations,
+        };
+        (result, usage)
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && self.is_enabled() {
+            self.record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if self.is_enabled() {
+            self.record_deallocation(layout.size());
+        }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && self.is_enabled() {
+            self.record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    /// A reallocation is counted as a new allocation of the new size, followed by the
+    /// deallocation of the previous block.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && self.is_enabled() {
+            self.record_allocation(new_size);
+            self.record_deallocation(layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Memory allocated during a single phase.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryUsage {
+    /// Highest amount of bytes allocated at once by the phase.
+    pub peak: usize,
+    /// Bytes allocated by the phase, freed or not.
+    pub total: usize,
+    pub allocations: usize,
+}
+
+/// Measured memory usage of a daily solution.
+#[derive(Clone, Debug)]
+pub struct MemoryReport {
+    pub day: u8,
+    pub parsing: MemoryUsage,
+    pub parts: Vec<(Part, MemoryUsage)>,
+}
+
+/// Run the solution once over provided input, measuring the memory allocated by input parsing
+/// and by each requested part separately. The parsed input is kept while the parts run, so
+/// that it is not part of their measures.
+///
+/// The input is read before measures, so that parsing measures do not include reading the
+/// source.
+pub fn profile_solution(
+    allocator: &CountingAllocator,
+    solution: &dyn DailySolution,
+    source: &InputSource,
+    parts: &[Part],
+) -> Result<MemoryReport, Error> {
+    let content = source.read_to_string()?;
+
+    let (daily_input, parsing) =
+        allocator.measure(|| solution.parse_input(&mut content.as_bytes()));
+    let daily_input = daily_input?;
+
+    let mut part_usages = Vec::with_capacity(parts.len());
+    for &part in parts {
+        let (answer, usage) = allocator.measure(|| daily_input.solve(part));
+        answer?;
+        part_usages.push((part, usage));
+    }
+
+    Ok(MemoryReport {
+        day: solution.day_number(),
+        parsing,
+        parts: part_usages,
+    })
+}
+
+/// Format an amount of bytes with a binary unit, such as `12.5 KiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod test_memory {
+    use std::alloc::{GlobalAlloc, Layout};
+
+    use super::{format_bytes, CountingAllocator, MemoryUsage};
+
+    #[test]
+    fn test_measure() {
+        let allocator = CountingAllocator::new();
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        let (_, usage) = allocator.measure(|| unsafe {
+            let ptr = allocator.alloc(layout);
+            allocator.dealloc(ptr, layout);
+        });
+        assert_eq!(usage, MemoryUsage::default());
+
+        allocator.set_enabled(true);
+        let (ptr, usage) = allocator.measure(|| unsafe {
+            let first = allocator.alloc(layout);
+            let second = allocator.alloc_zeroed(layout);
+            allocator.dealloc(second, layout);
+            allocator.realloc(first, layout, 256)
+        });
+        assert_eq!(
+            usage,
+            MemoryUsage {
+                peak: 320,
+                total: 384,
+                allocations: 3,
+            }
+        );
+
+        let (_, usage) = allocator.measure(|| unsafe {
+            allocator.dealloc(ptr, Layout::from_size_align(256, 8).unwrap())
+        });
+        assert_eq!(usage, MemoryUsage::default());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,284 @@
+//! This module tracks the progress over the event calendar.
+//! The state of each part is stored into a text file, named `progress` in the inputs directory by
+//! default, with one `<day>:<part> <flag>[,<flag>]` entry per line, where flags are `verified`
+//! when the answer matches the expected one and `star` when the answer has been accepted.
+//! Empty lines and lines starting with `#` are ignored.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{day_part_entries, split_fields, Error, ParseError, ParseErrorKind, Part};
+
+/// The number of days of the event calendar.
+pub const CALENDAR_DAYS: u8 = 25;
+
+/// The recorded state of a day part.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PartProgress {
+    pub verified: bool,
+    pub star: bool,
+}
+
+/// The recorded state of every day part.
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+    parts: BTreeMap<(u8, Part), PartProgress>,
+}
+
+impl Progress {
+    /// The progress file of provided inputs directory.
+    pub fn default_path(inputs_directory: &Path) -> PathBuf {
+        inputs_directory.join("progress")
+    }
+
+    /// Load the progress from provided file, where no file means that nothing has been done yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_content(&content),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Read the progress from the content of a progress file, where unknown flags are rejected.
+    pub fn from_content(content: &str) -> Result<Self, Error> {
+        let mut progress = Self::default();
+
+        for entry in day_part_entries(content) {
+            let entry = entry?;
+            let mut part_progress = PartProgress::default();
+
+            for (column, flag) in split_fields(entry.value, ',') {
+                match flag {
+                    "verified" => part_progress.verified = true,
+                    "star" => part_progress.star = true,
+                    _ => {
+                        return Err(ParseError::new(
+                            None,
+                            entry.line,
+                            entry.value_column + column - 1,
+                            flag,
+                            ParseErrorKind::InvalidFormat,
+                        )
+                        .into())
+                    }
+                }
+            }
+            progress
+                .parts
+                .insert((entry.day, entry.part), part_progress);
+        }
+
+        Ok(progress)
+    }
+
+    /// The content of the progress file, sorted by day and part.
+    pub fn to_content(&self) -> String {
+        let mut content = String::new();
+
+        for ((day, part), part_progress) in self.parts.iter() {
+            let flags: Vec<&str> = [
+                (part_progress.verified, "verified"),
+                (part_progress.star, "star"),
+            ]
+            .into_iter()
+            .filter_map(|(is_set, flag)| is_set.then_some(flag))
+            .collect();
+            if !flags.is_empty() {
+                writeln!(content, "{}:{} {}", day, part, flags.join(",")).unwrap();
+            }
+        }
+
+        content
+    }
+
+    /// Write the progress to provided file.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, self.to_content())?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> PartProgress {
+        self.parts.get(&(day, part)).copied().unwrap_or_default()
+    }
+
+    pub fn set_verified(&mut self, day: u8, part: Part, verified: bool) {
+        self.parts.entry((day, part)).or_default().verified = verified;
+    }
+
+    pub fn set_star(&mut self, day: u8, part: Part) {
+        self.parts.entry((day, part)).or_default().star = true;
+    }
+
+    /// The number of stars earned.
+    pub fn stars(&self) -> usize {
+        self.parts
+            .values()
+            .filter(|part_progress| part_progress.star)
+            .count()
+    }
+}
+
+/// The numbers of the day modules stored into provided days directory, such as *src/days*,
+/// sorted by day number.
+pub fn day_modules(days_directory: &Path) -> Result<Vec<u8>, Error> {
+    let mut days = Vec::new();
+
+    for entry in fs::read_dir(days_directory)? {
+        let file_name = entry?.file_name();
+        let day = file_name
+            .to_str()
+            .and_then(|name| name.strip_prefix("day_")?.strip_suffix(".rs"))
+            .and_then(|number| number.parse::<u8>().ok());
+        if let Some(day) = day {
+            days.push(day);
+        }
+    }
+    days.sort_unstable();
+
+    Ok(days)
+}
+
+/// The symbol of a part in the calendar.
+fn part_symbol(is_implemented: bool, part_progress: PartProgress) -> char {
+    if part_progress.star {
+        '*'
+    } else if part_progress.verified {
+        'v'
+    } else if is_implemented {
+        'o'
+    } else {
+        '.'
+    }
+}
+
+/// Render the event calendar as a grid of five days per row, with the state of both parts of
+/// each day, followed by totals and a legend.
+pub fn calendar(progress: &Progress, implemented_days: &[u8]) -> String {
+    let mut grid = String::new();
+
+    for day in 1..=CALENDAR_DAYS {
+        let is_implemented = implemented_days.contains(&day);
+        let symbols: String = Part::ALL
+            .iter()
+            .map(|&part| part_symbol(is_implemented, progress.get(day, part)))
+            .collect();
+
+        write!(grid, "{:>2} {}", day, symbols).unwrap();
+        grid.push_str(if day.is_multiple_of(5) { "\n" } else { "   " });
+    }
+
+    let verified_parts = (1..=CALENDAR_DAYS)
+        .flat_map(|day| Part::ALL.iter().map(move |&part| (day, part)))
+        .filter(|&(day, part)| progress.get(day, part).verified)
+        .count();
+    writeln!(
+        grid,
+        "\nStars: {}/{}  Implemented days: {}/{}  Verified parts: {}/{}",
+        progress.stars(),
+        CALENDAR_DAYS as usize * Part::ALL.len(),
+        implemented_days.len(),
+        CALENDAR_DAYS,
+        verified_parts,
+        CALENDAR_DAYS as usize * Part::ALL.len()
+    )
+    .unwrap();
+    grid.push_str("Legend: * star, v verified, o implemented, . not implemented\n");
+
+    grid
+}
+
+#[cfg(test)]
+mod test_progress {
+    use std::fs;
+
+    use super::{calendar, day_modules, PartProgress, Progress};
+    use crate::{expect_parse_error, ParseErrorKind, Part};
+
+    const TEST_CONTENT: &str = "# day:part flags
+1:1 verified,star
+1:2 star
+3:1 verified
+";
+
+    #[test]
+    fn test_load_progress() {
+        let progress = Progress::from_content(TEST_CONTENT).unwrap();
+
+        assert_eq!(
+            progress.get(1, Part::One),
+            PartProgress {
+                verified: true,
+                star: true
+            }
+        );
+        assert_eq!(progress.get(3, Part::Two), PartProgress::default());
+        assert_eq!(progress.stars(), 2);
+        assert_eq!(
+            progress.to_content(),
+            "1:1 verified,star\n1:2 star\n3:1 verified\n"
+        );
+
+        let err = expect_parse_error(Progress::from_content("1:1 verified,gold"));
+        assert_eq!((err.line, err.column), (1, 14));
+        assert_eq!(err.text, "gold");
+        assert_eq!(err.kind, ParseErrorKind::InvalidFormat);
+
+        for content in ["0:1 star", "26:2 verified"] {
+            let err = expect_parse_error(Progress::from_content(content));
+            assert_eq!((err.line, err.column), (1, 1));
+            assert_eq!(err.kind, ParseErrorKind::OutOfRange);
+        }
+    }
+
+    #[test]
+    fn test_update_progress() {
+        let mut progress = Progress::from_content(TEST_CONTENT).unwrap();
+        progress.set_verified(1, Part::One, false);
+        progress.set_verified(3, Part::One, false);
+        progress.set_star(2, Part::Two);
+
+        assert_eq!(progress.to_content(), "1:1 star\n1:2 star\n2:2 star\n");
+    }
+
+    #[test]
+    fn test_day_modules() {
+        let days_directory =
+            std::env::temp_dir().join(format!("aoc_progress_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&days_directory);
+        fs::create_dir_all(&days_directory).unwrap();
+        for file_name in ["mod.rs", "day_12.rs", "day_3.rs", "day_x.rs", "day_4.txt"] {
+            fs::write(days_directory.join(file_name), "").unwrap();
+        }
+
+        assert_eq!(day_modules(&days_directory).unwrap(), vec![3, 12]);
+
+        fs::remove_dir_all(&days_directory).unwrap();
+        assert!(day_modules(&days_directory).is_err());
+    }
+
+    #[test]
+    fn test_calendar() {
+        let progress = Progress::from_content(TEST_CONTENT).unwrap();
+
+        assert_eq!(
+            calendar(&progress, &[1, 2, 3]),
+            " 1 **    2 oo    3 vo    4 ..    5 ..
+ 6 ..    7 ..    8 ..    9 ..   10 ..
+11 ..   12 ..   13 ..   14 ..   15 ..
+16 ..   17 ..   18 ..   19 ..   20 ..
+21 ..   22 ..   23 ..   24 ..   25 ..
+
+Stars: 2/50  Implemented days: 3/25  Verified parts: 2/50
+Legend: * star, v verified, o implemented, . not implemented
+"
+        );
+    }
+}
@@ -0,0 +1,477 @@
+//! This module records the outcome of daily solution runs.
+//! Records can be rendered as human text, JSON or CSV, one record per day and part.
+
+use std::fmt::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::input::InputSource;
+use crate::{log, Answer, DailySolution, Error, Part, PartAnswer};
+
+/// The outcome of a single day part.
+#[derive(Clone, Debug)]
+pub struct RunRecord {
+    pub day: u8,
+    pub part: Part,
+    pub input: InputSource,
+    /// Time spent solving the part, input parsing excluded.
+    pub elapsed: Duration,
+    pub outcome: Result<PartAnswer, String>,
+}
+
+impl RunRecord {
+    /// The answer of the part as displayed, or the error message if the part failed.
+    pub fn answer_text(&self) -> String {
+        match &self.outcome {
+            Ok(part_answer) => part_answer.answer.to_string(),
+            Err(err) => err.clone(),
+        }
+    }
+}
+
+/// Run provided closure, turning both its error and its panic into an error message.
+fn isolate<T, F>(f: F) -> Result<T, String>
+where
+    F: FnOnce() -> Result<T, Error>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result.map_err(|err| err.to_string()),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("panicked: {}", message))
+        }
+    }
+}
+
+/// Run the requested parts of a solution over provided input, timing each part.
+/// When the input cannot be parsed, every requested part is recorded with the parse error.
+/// A panic of the solution is recorded as an error too.
+pub fn run_day(
+    solution: &dyn DailySolution,
+    source: &InputSource,
+    parts: &[Part],
+) -> Vec<RunRecord> {
+    let new_record = |part, elapsed, outcome| RunRecord {
+        day: solution.day_number(),
+        part,
+        input: source.clone(),
+        elapsed,
+        outcome,
+    };
+
+    let day = Some(solution.day_number());
+    log::info(day, format!("reading input '{}'", source));
+
+    let start = Instant::now();
+    let daily_input = match isolate(|| solution.parse_source(source)) {
+        Ok(daily_input) => {
+            log::info(
+                day,
+                format!(
+                    "parsed {} record(s) in {:?}",
+                    daily_input.record_count(),
+                    start.elapsed()
+                ),
+            );
+            daily_input
+        }
+        Err(err) => {
+            log::error(day, format!("cannot parse input: {}", err));
+            return parts
+                .iter()
+                .map(|&part| new_record(part, Duration::ZERO, Err(err.clone())))
+                .collect();
+        }
+    };
+
+    parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let outcome = isolate(|| daily_input.solve(part));
+            let elapsed = start.elapsed();
+            match &outcome {
+                Ok(part_answer) => log::info(
+                    day,
+                    format!("part {} solved in {:?}: {}", part, elapsed, part_answer),
+                ),
+                Err(err) => log::error(day, format!("part {} failed: {}", part, err)),
+            }
+
+            new_record(part, elapsed, outcome)
+        })
+        .collect()
+}
+
+/// Run several days over up to `jobs` threads. `on_day` is called from the current thread
+/// with the records of each day, following the order of `days` whatever the completion order.
+pub fn run_days<F>(
+    days: &[(&dyn DailySolution, InputSource)],
+    parts: &[Part],
+    jobs: usize,
+    mut on_day: F,
+) where
+    F: FnMut(&dyn DailySolution, Vec<RunRecord>),
+{
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let sender = sender.clone();
+            let next_index = &next_index;
+
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+                let Some((solution, source)) = days.get(index) else {
+                    break;
+                };
+
+                if sender
+                    .send((index, run_day(*solution, source, parts)))
+                    .is_err()
+                {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending: Vec<Option<Vec<RunRecord>>> = vec![None; days.len()];
+        let mut next_to_report = 0;
+
+        for (index, records) in receiver {
+            pending[index] = Some(records);
+
+            while let Some(records) = pending.get_mut(next_to_report).and_then(Option::take) {
+                on_day(days[next_to_report].0, records);
+                next_to_report += 1;
+            }
+        }
+    });
+}
+
+/// The available renderings of run records.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(format!(
+                "unknown output format '{}', expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Render provided records with this format.
+    pub fn render(self, records: &[RunRecord]) -> String {
+        match self {
+            Self::Text => to_text(records),
+            Self::Json => to_json(records),
+            Self::Csv => to_csv(records),
+        }
+    }
+}
+
+/// Render records as the human sentences of each part.
+pub fn to_text(records: &[RunRecord]) -> String {
+    let mut text = String::new();
+
+    for record in records {
+        match &record.outcome {
+            Ok(part_answer) => writeln!(text, "{}", part_answer),
+            Err(err) => writeln!(
+                text,
+                "Day {} part {} failed: {}",
+                record.day, record.part, err
+            ),
+        }
+        .unwrap();
+    }
+
+    text
+}
+
+/// Render records as a JSON array, with an object per record.
+pub fn to_json(records: &[RunRecord]) -> String {
+    let objects: Vec<String> = records
+        .iter()
+        .map(|record| {
+            let (label, answer, error) = match &record.outcome {
+                Ok(part_answer) => (
+                    json_string(part_answer.label),
+                    json_answer(&part_answer.answer),
+                    String::from("null"),
+                ),
+                Err(err) => (String::from("null"), String::from("null"), json_string(err)),
+            };
+
+            format!(
+                "{{\"day\":{},\"part\":{},\"label\":{},\"answer\":{},\"error\":{},\"input_path\":{},\"elapsed_us\":{}}}",
+                record.day,
+                record.part,
+                label,
+                answer,
+                error,
+                json_string(&record.input.to_string()),
+                record.elapsed.as_micros()
+            )
+        })
+        .collect();
+
+    if objects.is_empty() {
+        return String::from("[]\n");
+    }
+
+    format!("[\n  {}\n]\n", objects.join(",\n  "))
+}
+
+/// Render records as CSV, with a header line.
+pub fn to_csv(records: &[RunRecord]) -> String {
+    let mut csv = String::from("day,part,label,answer,error,input_path,elapsed_us\n");
+
+    for record in records {
+        let (label, answer, error) = match &record.outcome {
+            Ok(part_answer) => (part_answer.label, part_answer.answer.to_string(), ""),
+            Err(err) => ("", String::new(), err.as_str()),
+        };
+
+        writeln!(
+            csv,
+            "{},{},{},{},{},{},{}",
+            record.day,
+            record.part,
+            csv_field(label),
+            csv_field(&answer),
+            csv_field(error),
+            csv_field(&record.input.to_string()),
+            record.elapsed.as_micros()
+        )
+        .unwrap();
+    }
+
+    csv
+}
+
+/// Render records as an aligned table with a row per day part, followed by a row for each
+/// selected day which is not implemented.
+pub fn summary_table(records: &[RunRecord], missing_days: &[u8]) -> String {
+    let mut rows: Vec<[String; 5]> = vec![[
+        String::from("Day"),
+        String::from("Part"),
+        String::from("Answer"),
+        String::from("Status"),
+        String::from("Duration"),
+    ]];
+
+    for record in records {
+        let status = match record.outcome {
+            Ok(_) => "ok",
+            Err(_) => "error",
+        };
+        rows.push([
+            record.day.to_string(),
+            record.part.to_string(),
+            record.answer_text(),
+            status.to_string(),
+            format!("{:?}", record.elapsed),
+        ]);
+    }
+    for day in missing_days {
+        rows.push([
+            day.to_string(),
+            String::from("-"),
+            String::from("-"),
+            String::from("not implemented"),
+            String::from("-"),
+        ]);
+    }
+
+    let mut widths = [0; 5];
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in rows.iter() {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join(" | ");
+        writeln!(table, "{}", line.trim_end()).unwrap();
+    }
+
+    table
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for char in value.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(value) => value.to_string(),
+        Answer::Signed(value) => value.to_string(),
+        Answer::Text(value) => json_string(value),
+        Answer::Tuple(values) => format!(
+            "[{}]",
+            values.iter().map(json_answer).collect::<Vec<_>>().join(",")
+        ),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test_report {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{isolate, run_days, summary_table, to_csv, to_json, OutputFormat, RunRecord};
+    use crate::days::SOLUTIONS;
+    use crate::input::InputSource;
+    use crate::{Answer, DailySolution, Error, Part, PartAnswer};
+
+    fn test_records() -> Vec<RunRecord> {
+        vec![
+            RunRecord {
+                day: 7,
+                part: Part::One,
+                input: InputSource::File(PathBuf::from("aoc_inputs/daily_input_7")),
+                elapsed: Duration::from_micros(42),
+                outcome: Ok(PartAnswer::new(
+                    Part::One,
+                    "Best position",
+                    (Answer::Signed(-2), 37),
+                )),
+            },
+            RunRecord {
+                day: 8,
+                part: Part::One,
+                input: InputSource::Stdin,
+                elapsed: Duration::ZERO,
+                outcome: Err(String::from("no \"such\" file")),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            to_json(&test_records()),
+            r#"[
+  {"day":7,"part":1,"label":"Best position","answer":[-2,37],"error":null,"input_path":"aoc_inputs/daily_input_7","elapsed_us":42},
+  {"day":8,"part":1,"label":null,"answer":null,"error":"no \"such\" file","input_path":"-","elapsed_us":0}
+]
+"#
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            to_csv(&test_records()),
+            r#"day,part,label,answer,error,input_path,elapsed_us
+7,1,Best position,"(-2, 37)",,aoc_inputs/daily_input_7,42
+8,1,,,"no ""such"" file",-,0
+"#
+        );
+    }
+
+    #[test]
+    fn test_summary_table() {
+        assert_eq!(
+            summary_table(&test_records(), &[9]),
+            r#"Day | Part | Answer         | Status          | Duration
+7   | 1    | (-2, 37)       | ok              | 42µs
+8   | 1    | no "such" file | error           | 0ns
+9   | -    | -              | not implemented | -
+"#
+        );
+    }
+
+    #[test]
+    fn test_isolate_panic() {
+        assert_eq!(isolate(|| Ok::<usize, Error>(42)), Ok(42));
+        assert_eq!(
+            isolate(|| -> Result<usize, Error> { panic!("no winner grid") }),
+            Err(String::from("panicked: no winner grid"))
+        );
+    }
+
+    #[test]
+    fn test_run_days_order() {
+        let missing_input = InputSource::File(PathBuf::from("/nonexistent/daily_input"));
+        let days: Vec<(&dyn DailySolution, InputSource)> = SOLUTIONS
+            .iter()
+            .rev()
+            .map(|&solution| (solution, missing_input.clone()))
+            .collect();
+
+        let mut reported_days = Vec::new();
+        run_days(&days, &[Part::One], 4, |solution, records| {
+            assert_eq!(records.len(), 1);
+            assert!(records[0].outcome.is_err());
+            reported_days.push(solution.day_number());
+        });
+
+        let expected_days: Vec<u8> = days
+            .iter()
+            .map(|(solution, _)| solution.day_number())
+            .collect();
+        assert_eq!(reported_days, expected_days);
+    }
+
+    #[test]
+    fn test_output_format() {
+        assert_eq!("JSON".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+}
@@ -0,0 +1,354 @@
+//! This module generates the skeleton of a new daily solution.
+//! The skeleton is made of the day module, stored into *src/days*, registered into
+//! [`days::SOLUTIONS`](crate::days::SOLUTIONS) behind a `day_<day_num>` feature declared into
+//! the package manifest, and of an empty input file placeholder.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::input::input_file_name;
+use crate::Error;
+
+/// The source of a day module, where `{day}` and `{title}` are replaced by the day values.
+const MODULE_TEMPLATE: &str = r#"//! This is the day {day} module.
+//! The instructions are available here: https://adventofcode.com/2021/day/{day}
+
+use std::io::BufRead;
+
+use crate::{DailyInput, DailySolution, Error, Part, PartAnswer};
+
+pub struct Solution;
+
+impl Solution {
+    pub const DAY_NUMBER: u8 = {day};
+}
+
+impl DailySolution for Solution {
+    fn day_number(&self) -> u8 {
+        Self::DAY_NUMBER
+    }
+
+    fn title(&self) -> &'static str {
+        {title}
+    }
+
+    fn parse_input(&self, reader: &mut dyn BufRead) -> Result<Box<dyn DailyInput>, Error> {
+        let lines = reader.lines().collect::<Result<Vec<String>, _>>()?;
+
+        Ok(Box::new(PuzzleInput(lines)))
+    }
+}
+
+/// The lines of the puzzle input.
+struct PuzzleInput(Vec<String>);
+
+impl DailyInput for PuzzleInput {
+    fn solve(&self, part: Part) -> Result<PartAnswer, Error> {
+        Ok(match part {
+            Part::One => PartAnswer::new(part, "Answer", part_1(&self.0)),
+            Part::Two => PartAnswer::new(part, "Answer", part_2(&self.0)),
+        })
+    }
+
+    fn record_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+fn part_1(_lines: &[String]) -> usize {
+    todo!()
+}
+
+fn part_2(_lines: &[String]) -> usize {
+    todo!()
+}
+
+#[cfg(test)]
+mod test_day {
+    /// Sample lines from guidelines
+    const TEST_LINES: &str = "";
+
+    crate::example_tests!(super::Solution, {
+        #[ignore = "fill in the example and its answers"]
+        test_guidelines: TEST_LINES => [0, 0],
+    });
+}
+"#;
+
+/// The files written while scaffolding a day.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Scaffold {
+    pub module_path: PathBuf,
+    pub registry_path: PathBuf,
+    pub manifest_path: PathBuf,
+    /// The input placeholder, or None if an input file already exists.
+    pub input_path: Option<PathBuf>,
+}
+
+/// Generate the source of a new day module.
+pub fn module_source(day: u8, title: &str) -> String {
+    MODULE_TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{title}", &format!("{:?}", title))
+}
+
+/// Insert a block of lines among the blocks declaring a day, keeping them sorted by day number.
+/// Each block ends with an item line, recognized by its prefix directly followed by the day
+/// number, and is preceded by its attributes, such as its feature gate.
+fn insert_day_block(lines: &mut Vec<String>, prefix: &str, day: u8, block: Vec<String>) -> bool {
+    let day_items: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let number = line.strip_prefix(prefix)?;
+            let end = number
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(number.len());
+            Some((index, number[..end].parse().ok()?))
+        })
+        .collect();
+
+    let index = match day_items.iter().find(|(_, number)| *number >= day) {
+        Some((_, number)) if *number == day => return false,
+        Some((index, _)) => index + 1 - block.len(),
+        None => match day_items.last() {
+            Some((index, _)) => index + 1,
+            None => return false,
+        },
+    };
+    lines.splice(index..index, block);
+
+    true
+}
+
+/// Apply insertions of day blocks to a source, or return None if one of them fails.
+fn insert_day_blocks(source: &str, insertions: Vec<(&str, u8, Vec<String>)>) -> Option<String> {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+
+    for (prefix, day, block) in insertions {
+        if !insert_day_block(&mut lines, prefix, day, block) {
+            return None;
+        }
+    }
+
+    let mut source = lines.join("\n");
+    source.push('\n');
+    Some(source)
+}
+
+/// Register a day module into the source of the days module, declaring the module and adding its
+/// solution to the registry, both gated by the feature of the day.
+/// Return None if the day is already registered or if the source has no day to be sorted among.
+pub fn register_day(registry_source: &str, day: u8) -> Option<String> {
+    let feature_gate = format!("#[cfg(feature = \"day_{}\")]", day);
+
+    insert_day_blocks(
+        registry_source,
+        vec![
+            (
+                "pub mod day_",
+                day,
+                vec![feature_gate.clone(), format!("pub mod day_{};", day)],
+            ),
+            (
+                "    &day_",
+                day,
+                vec![
+                    format!("    {}", feature_gate),
+                    format!("    &day_{}::Solution,", day),
+                ],
+            ),
+        ],
+    )
+}
+
+/// Register the feature of a day into the source of the package manifest, declaring the feature
+/// and enabling it by the `full` feature.
+/// Return None if the feature is already declared or if the manifest has no day feature.
+pub fn register_feature(manifest_source: &str, day: u8) -> Option<String> {
+    insert_day_blocks(
+        manifest_source,
+        vec![
+            ("    \"day_", day, vec![format!("    \"day_{}\",", day)]),
+            ("day_", day, vec![format!("day_{} = []", day)]),
+        ],
+    )
+}
+
+/// Scaffold a new day into the project stored at provided directory, and declare its feature.
+/// Nothing is written if the day module already exists or if the day is already registered.
+/// The input placeholder is only created if there is no input file for the day yet.
+pub fn scaffold_day(
+    project_directory: &Path,
+    inputs_directory: &Path,
+    day: u8,
+    title: &str,
+) -> Result<Scaffold, Error> {
+    let days_directory = project_directory.join("src").join("days");
+    let module_path = days_directory.join(format!("day_{}.rs", day));
+    let registry_path = days_directory.join("mod.rs");
+    let manifest_path = project_directory.join("Cargo.toml");
+
+    if module_path.exists() {
+        return Err(Error::AlreadyExists(format!(
+            "day module '{}'",
+            module_path.display()
+        )));
+    }
+    let cannot_register = |path: &Path| {
+        Error::AlreadyExists(format!(
+            "registration of day {} into '{}'",
+            day,
+            path.display()
+        ))
+    };
+    let registry_source = register_day(&fs::read_to_string(&registry_path)?, day)
+        .ok_or_else(|| cannot_register(&registry_path))?;
+    let manifest_source = register_feature(&fs::read_to_string(&manifest_path)?, day)
+        .ok_or_else(|| cannot_register(&manifest_path))?;
+
+    fs::write(&module_path, module_source(day, title))?;
+    fs::write(&registry_path, registry_source)?;
+    fs::write(&manifest_path, manifest_source)?;
+
+    let input_path = inputs_directory.join(input_file_name(day));
+    let input_path = if input_path.exists() {
+        None
+    } else {
+        fs::create_dir_all(inputs_directory)?;
+        fs::write(&input_path, "")?;
+        Some(input_path)
+    };
+
+    Ok(Scaffold {
+        module_path,
+        registry_path,
+        manifest_path,
+        input_path,
+    })
+}
+
+#[cfg(test)]
+mod test_scaffold {
+    use std::fs;
+
+    use super::{module_source, register_day, register_feature, scaffold_day};
+
+    const TEST_REGISTRY: &str = r#"#[cfg(feature = "day_1")]
+pub mod day_1;
+#[cfg(feature = "day_3")]
+pub mod day_3;
+
+pub static SOLUTIONS: &[&dyn DailySolution] = &[
+    #[cfg(feature = "day_1")]
+    &day_1::Solution,
+    #[cfg(feature = "day_3")]
+    &day_3::Solution,
+];
+
+#[cfg(test)]
+mod test_days {
+    #[test]
+    #[cfg(feature = "day_3")]
+    fn test_day_3() {}
+}
+"#;
+
+    const TEST_MANIFEST: &str = r#"[features]
+default = ["full"]
+full = [
+    "cli",
+    "day_1",
+    "day_3",
+]
+cli = ["dep:clap"]
+day_1 = []
+day_3 = []
+"#;
+
+    #[test]
+    fn test_register_day() {
+        let registry = register_day(TEST_REGISTRY, 2).unwrap();
+        assert!(registry.starts_with(
+            r#"#[cfg(feature = "day_1")]
+pub mod day_1;
+#[cfg(feature = "day_2")]
+pub mod day_2;
+#[cfg(feature = "day_3")]
+pub mod day_3;
+
+pub static SOLUTIONS: &[&dyn DailySolution] = &[
+    #[cfg(feature = "day_1")]
+    &day_1::Solution,
+    #[cfg(feature = "day_2")]
+    &day_2::Solution,
+    #[cfg(feature = "day_3")]
+    &day_3::Solution,
+];
+"#
+        ));
+
+        let registry = register_day(TEST_REGISTRY, 12).unwrap();
+        assert!(
+            registry.contains("pub mod day_3;\n#[cfg(feature = \"day_12\")]\npub mod day_12;\n")
+        );
+        assert!(registry.contains("    &day_12::Solution,\n];"));
+        assert!(registry.ends_with("    fn test_day_3() {}\n}\n"));
+
+        assert_eq!(register_day(TEST_REGISTRY, 3), None);
+        assert_eq!(register_day("", 3), None);
+    }
+
+    #[test]
+    fn test_register_feature() {
+        let manifest = register_feature(TEST_MANIFEST, 2).unwrap();
+        assert!(manifest.contains("    \"day_1\",\n    \"day_2\",\n    \"day_3\",\n"));
+        assert!(manifest.ends_with("day_1 = []\nday_2 = []\nday_3 = []\n"));
+
+        assert_eq!(register_feature(TEST_MANIFEST, 1), None);
+    }
+
+    #[test]
+    fn test_module_source() {
+        let source = module_source(8, "Seven \"Segment\" Search");
+
+        assert!(source.contains("https://adventofcode.com/2021/day/8\n"));
+        assert!(source.contains("pub const DAY_NUMBER: u8 = 8;"));
+        assert!(source.contains("        \"Seven \\\"Segment\\\" Search\"\n"));
+    }
+
+    #[test]
+    fn test_scaffold_day() {
+        let project_directory =
+            std::env::temp_dir().join(format!("aoc_scaffold_test_{}", std::process::id()));
+        let days_directory = project_directory.join("src").join("days");
+        let inputs_directory = project_directory.join("aoc_inputs");
+        let _ = fs::remove_dir_all(&project_directory);
+        fs::create_dir_all(&days_directory).unwrap();
+        fs::write(days_directory.join("mod.rs"), TEST_REGISTRY).unwrap();
+        fs::write(project_directory.join("Cargo.toml"), TEST_MANIFEST).unwrap();
+
+        let scaffold = scaffold_day(&project_directory, &inputs_directory, 2, "Dive!").unwrap();
+        assert_eq!(scaffold.module_path, days_directory.join("day_2.rs"));
+        assert_eq!(
+            scaffold.input_path,
+            Some(inputs_directory.join("daily_input_2"))
+        );
+        assert!(fs::read_to_string(&scaffold.registry_path)
+            .unwrap()
+            .contains("&day_2::Solution"));
+        assert!(fs::read_to_string(&scaffold.manifest_path)
+            .unwrap()
+            .contains("day_2 = []"));
+        assert_eq!(
+            fs::read_to_string(inputs_directory.join("daily_input_2")).unwrap(),
+            ""
+        );
+
+        assert!(scaffold_day(&project_directory, &inputs_directory, 2, "Dive!").is_err());
+        assert!(scaffold_day(&project_directory, &inputs_directory, 3, "Binary").is_err());
+        assert!(!days_directory.join("day_3.rs").exists());
+
+        fs::remove_dir_all(&project_directory).unwrap();
+    }
+}
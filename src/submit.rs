@@ -0,0 +1,568 @@
+//! This module submits puzzle answers to an HTTP endpoint, and keeps the history of attempts.
+//!
+//! Answers are posted as the puzzle website expects them, to `<endpoint>/2021/day/<day>/answer`
+//! with a `level=<part>&answer=<answer>` form. Only plain HTTP endpoints are supported: by
+//! default, answers are sent to a local stand-in of the website expected to listen on
+//! [`DEFAULT_ENDPOINT`], which can be started with `cargo run --example mock_server`.
+//!
+//! Attempts are stored into a text file, named `submissions` in the inputs directory by default,
+//! with one `<day>:<part> <outcome> <answer>` entry per line, so that a known wrong answer is
+//! never submitted again.
+
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{day_part_entries, log, Error, ParseError, ParseErrorKind, Part};
+
+/// The endpoint used when none is provided.
+pub const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:8021";
+
+/// Environment variable naming the endpoint.
+pub const ENDPOINT_VAR: &str = "AOC_SUBMIT_URL";
+
+/// Environment variable holding the session cookie sent along answers, if any. As answers go over
+/// plain HTTP, the cookie is only sent to loopback endpoints.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The endpoint used when none is explicitly provided.
+pub fn default_endpoint() -> String {
+    match std::env::var(ENDPOINT_VAR) {
+        Ok(endpoint) if !endpoint.is_empty() => endpoint,
+        _ => String::from(DEFAULT_ENDPOINT),
+    }
+}
+
+/// The address of a plain HTTP endpoint, as `http://<host>[:<port>][/<path>]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Endpoint {
+    pub host: String,
+    pub port: u16,
+    /// The path prefix of the requests, without trailing slash.
+    pub path: String,
+}
+
+impl Endpoint {
+    /// Whether the endpoint is reached without leaving the local machine.
+    pub fn is_loopback(&self) -> bool {
+        let host = self.host.trim_start_matches('[').trim_end_matches(']');
+
+        host.eq_ignore_ascii_case("localhost")
+            || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+    }
+
+    /// The path of the answer form of provided day.
+    pub fn answer_path(&self, day: u8) -> String {
+        format!("{}/2021/day/{}/answer", self.path, day)
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let address = s
+            .strip_prefix("http://")
+            .ok_or_else(|| format!("expected a http:// endpoint, found '{}'", s))?;
+        let (authority, path) = match address.find('/') {
+            Some(index) => address.split_at(index),
+            None => (address, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>()
+                    .map_err(|_| format!("invalid port in endpoint '{}'", s))?,
+            ),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(format!("missing host in endpoint '{}'", s));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+/// The verdict given to a submitted answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, without hint.
+    Wrong,
+    /// The answer has not been checked, as the previous one was submitted too recently.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part has already been solved, so that the answer has not been checked.
+    AlreadySolved,
+    /// The response cannot be understood.
+    Unknown,
+}
+
+impl SubmitOutcome {
+    /// Whether the answer has been checked and rejected.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+
+    /// The name of the outcome, as stored into the history file.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too-high",
+            Self::TooLow => "too-low",
+            Self::Wrong => "wrong",
+            Self::RateLimited { .. } => "rate-limited",
+            Self::AlreadySolved => "already-solved",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "correct" => Self::Correct,
+            "too-high" => Self::TooHigh,
+            "too-low" => Self::TooLow,
+            "wrong" => Self::Wrong,
+            "rate-limited" => Self::RateLimited { wait: None },
+            "already-solved" => Self::AlreadySolved,
+            "unknown" => Self::Unknown,
+            _ => return None,
+        })
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => f.write_str("correct"),
+            Self::TooHigh => f.write_str("too high"),
+            Self::TooLow => f.write_str("too low"),
+            Self::Wrong => f.write_str("wrong"),
+            Self::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            Self::RateLimited { wait: None } => f.write_str("rate limited"),
+            Self::AlreadySolved => f.write_str("already solved"),
+            Self::Unknown => f.write_str("unknown response"),
+        }
+    }
+}
+
+/// Read the wait delay of a rate limit message, such as `you have 1m 30s left to wait`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let end = text.find(" left to wait")?;
+    let start = text[..end].rfind("you have ")? + "you have ".len();
+
+    let mut seconds = 0;
+    for amount in text[start..end].split_whitespace() {
+        let (value, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Interpret the page returned for a submitted answer.
+pub fn interpret_response(body: &str) -> SubmitOutcome {
+    let text = body.to_lowercase().replace('\u{2019}', "'");
+
+    if text.contains("that's the right answer") {
+        SubmitOutcome::Correct
+    } else if text.contains("too recently") {
+        SubmitOutcome::RateLimited {
+            wait: parse_wait(&text),
+        }
+    } else if text.contains("too high") {
+        SubmitOutcome::TooHigh
+    } else if text.contains("too low") {
+        SubmitOutcome::TooLow
+    } else if text.contains("not the right answer") {
+        SubmitOutcome::Wrong
+    } else if text.contains("solving the right level") {
+        SubmitOutcome::AlreadySolved
+    } else {
+        SubmitOutcome::Unknown
+    }
+}
+
+/// Encode a form value, escaping every byte which is not alphanumeric.
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'-' | b'.' | b'_' => {
+                (byte as char).to_string()
+            }
+            b' ' => String::from("+"),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Read a HTTP message head from provided reader, returning its first line and its headers.
+fn read_head<R: BufRead>(reader: &mut R) -> Result<(String, Vec<(String, String)>), Error> {
+    let mut first_line = String::new();
+    reader.read_line(&mut first_line)?;
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    Ok((first_line.trim_end().to_string(), headers))
+}
+
+/// Post an answer to provided endpoint and interpret the response.
+pub fn submit_answer(
+    endpoint: &Endpoint,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<SubmitOutcome, Error> {
+    let body = format!("level={}&answer={}", part, form_encode(answer));
+    let cookie = match std::env::var(SESSION_VAR) {
+        Ok(session) if !session.is_empty() && endpoint.is_loopback() => {
+            format!("Cookie: session={}\r\n", session)
+        }
+        Ok(session) if !session.is_empty() => {
+            log::warn(
+                Some(day),
+                format!(
+                    "${} not sent to non-loopback host '{}'",
+                    SESSION_VAR, endpoint.host
+                ),
+            );
+            String::new()
+        }
+        _ => String::new(),
+    };
+
+    let mut stream = TcpStream::connect((endpoint.host.as_str(), endpoint.port))?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/x-www-form-urlencoded\r\n\
+         Content-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        endpoint.answer_path(day),
+        endpoint.host,
+        endpoint.port,
+        body.len(),
+        cookie,
+        body
+    )?;
+
+    let mut reader = BufReader::new(stream);
+    let (status_line, _) = read_head(&mut reader)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or_else(|| Error::UnexpectedResponse(status_line.clone()))?;
+    if !(200..300).contains(&status) {
+        return Err(Error::UnexpectedResponse(status_line));
+    }
+
+    let mut response = String::new();
+    reader.read_to_string(&mut response)?;
+
+    Ok(interpret_response(&response))
+}
+
+/// A submitted answer and the verdict it received.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: Part,
+    pub outcome: SubmitOutcome,
+    pub answer: String,
+}
+
+impl Display for Attempt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{} {} {}",
+            self.day,
+            self.part,
+            self.outcome.name(),
+            self.answer
+        )
+    }
+}
+
+/// The answers submitted so far, in submission order.
+#[derive(Clone, Debug, Default)]
+pub struct SubmissionHistory {
+    attempts: Vec<Attempt>,
+}
+
+impl SubmissionHistory {
+    /// The submission history file of provided inputs directory.
+    pub fn default_path(inputs_directory: &Path) -> PathBuf {
+        inputs_directory.join("submissions")
+    }
+
+    /// Load the history from provided file, where no file means that nothing has been submitted.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_content(&content),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Read the history from the content of a history file, where every attempt must have a
+    /// known outcome and an answer.
+    pub fn from_content(content: &str) -> Result<Self, Error> {
+        let mut attempts = Vec::new();
+
+        for entry in day_part_entries(content) {
+            let entry = entry?;
+            let (outcome, answer) = entry.value.split_once(' ').unwrap_or((entry.value, ""));
+            let outcome = SubmitOutcome::from_name(outcome)
+                .filter(|_| !answer.trim().is_empty())
+                .ok_or_else(|| {
+                    ParseError::new(
+                        None,
+                        entry.line,
+                        entry.value_column,
+                        entry.value,
+                        ParseErrorKind::InvalidFormat,
+                    )
+                })?;
+
+            attempts.push(Attempt {
+                day: entry.day,
+                part: entry.part,
+                outcome,
+                answer: answer.trim().to_string(),
+            });
+        }
+
+        Ok(Self { attempts })
+    }
+
+    /// The attempts of provided day and part, in submission order.
+    pub fn attempts(&self, day: u8, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |attempt| attempt.day == day && attempt.part == part)
+    }
+
+    /// The outcome already known for an answer, if it would not need to be submitted.
+    ///
+    /// An answer is known when it has been checked before, when the part has been solved by
+    /// another answer, or when it is a number beyond an answer found too high or too low.
+    pub fn known_outcome(&self, day: u8, part: Part, answer: &str) -> Option<SubmitOutcome> {
+        let value = answer.parse::<u128>().ok();
+
+        for attempt in self.attempts(day, part) {
+            match &attempt.outcome {
+                SubmitOutcome::Correct if attempt.answer == answer => {
+                    return Some(SubmitOutcome::Correct)
+                }
+                SubmitOutcome::Correct => return Some(SubmitOutcome::Wrong),
+                outcome if outcome.is_wrong() && attempt.answer == answer => {
+                    return Some(outcome.clone())
+                }
+                _ => (),
+            }
+
+            let attempt_value = attempt.answer.parse::<u128>().ok();
+            if let (Some(value), Some(attempt_value)) = (value, attempt_value) {
+                match attempt.outcome {
+                    SubmitOutcome::TooHigh if value >= attempt_value => {
+                        return Some(SubmitOutcome::TooHigh)
+                    }
+                    SubmitOutcome::TooLow if value <= attempt_value => {
+                        return Some(SubmitOutcome::TooLow)
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Record a new attempt, and append it to provided history file.
+    pub fn record(&mut self, path: &Path, attempt: Attempt) -> Result<(), Error> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", attempt)?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_submit {
+    use std::time::Duration;
+
+    use super::{
+        form_encode, interpret_response, Attempt, Endpoint, SubmissionHistory, SubmitOutcome,
+    };
+    use crate::Part;
+
+    #[test]
+    fn test_endpoint_from_str() {
+        assert_eq!(
+            "http://127.0.0.1:8021".parse::<Endpoint>(),
+            Ok(Endpoint {
+                host: String::from("127.0.0.1"),
+                port: 8021,
+                path: String::new(),
+            })
+        );
+
+        let endpoint = "http://localhost/aoc/".parse::<Endpoint>().unwrap();
+        assert_eq!((endpoint.host.as_str(), endpoint.port), ("localhost", 80));
+        assert_eq!(endpoint.answer_path(7), "/aoc/2021/day/7/answer");
+
+        assert!(endpoint.is_loopback());
+        assert!("http://127.0.0.2:8021"
+            .parse::<Endpoint>()
+            .unwrap()
+            .is_loopback());
+        assert!("http://[::1]:8021"
+            .parse::<Endpoint>()
+            .unwrap()
+            .is_loopback());
+        assert!(!"http://adventofcode.com"
+            .parse::<Endpoint>()
+            .unwrap()
+            .is_loopback());
+        assert!(!"http://10.0.0.1".parse::<Endpoint>().unwrap().is_loopback());
+
+        assert!("https://adventofcode.com".parse::<Endpoint>().is_err());
+        assert!("http://:80".parse::<Endpoint>().is_err());
+    }
+
+    #[test]
+    fn test_interpret_response() {
+        assert_eq!(
+            interpret_response("<p>That's the right answer!  You are one gold star closer.</p>"),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            interpret_response("<p>That's not the right answer; your answer is too high.</p>"),
+            SubmitOutcome::TooHigh
+        );
+        assert_eq!(
+            interpret_response(
+                "<p>That\u{2019}s not the right answer; your answer is too low.</p>"
+            ),
+            SubmitOutcome::TooLow
+        );
+        assert_eq!(
+            interpret_response("<p>That's not the right answer.</p>"),
+            SubmitOutcome::Wrong
+        );
+        assert_eq!(
+            interpret_response(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again. You have 1m 5s left to wait."
+            ),
+            SubmitOutcome::RateLimited {
+                wait: Some(Duration::from_secs(65))
+            }
+        );
+        assert_eq!(
+            interpret_response("You don't seem to be solving the right level."),
+            SubmitOutcome::AlreadySolved
+        );
+        assert_eq!(interpret_response("Not found"), SubmitOutcome::Unknown);
+    }
+
+    #[test]
+    fn test_form_encoding() {
+        assert_eq!(form_encode("(2, 37)"), "%282%2C+37%29");
+        assert_eq!(form_encode("a-b_c.d"), "a-b_c.d");
+    }
+
+    #[test]
+    fn test_known_outcome() {
+        let history = SubmissionHistory::from_content(
+            "1:1 too-low 1500
+1:1 rate-limited 1700
+1:1 too-high 1800
+1:2 wrong ABC
+1:2 correct DEF
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            history.known_outcome(1, Part::One, "1499"),
+            Some(SubmitOutcome::TooLow)
+        );
+        assert_eq!(
+            history.known_outcome(1, Part::One, "1801"),
+            Some(SubmitOutcome::TooHigh)
+        );
+        assert_eq!(history.known_outcome(1, Part::One, "1700"), None);
+        assert_eq!(
+            history.known_outcome(1, Part::Two, "ABC"),
+            Some(SubmitOutcome::Wrong)
+        );
+        assert_eq!(
+            history.known_outcome(1, Part::Two, "DEF"),
+            Some(SubmitOutcome::Correct)
+        );
+        assert_eq!(
+            history.known_outcome(1, Part::Two, "GHI"),
+            Some(SubmitOutcome::Wrong)
+        );
+        assert_eq!(history.known_outcome(2, Part::One, "1"), None);
+
+        assert!(SubmissionHistory::from_content("1:1 too-big 5").is_err());
+    }
+
+    #[test]
+    fn test_record_attempt() {
+        let path = std::env::temp_dir().join(format!("aoc_submissions_{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut history = SubmissionHistory::load(&path).unwrap();
+        let attempt = Attempt {
+            day: 7,
+            part: Part::Two,
+            outcome: SubmitOutcome::TooLow,
+            answer: String::from("(5, 168)"),
+        };
+        history.record(&path, attempt.clone()).unwrap();
+
+        let history = SubmissionHistory::load(&path).unwrap();
+        assert_eq!(
+            history.attempts(7, Part::Two).collect::<Vec<&Attempt>>(),
+            vec![&attempt]
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+//! This module checks daily answers against recorded expected results.
+//! Expected results are stored into a text file, named `expected_answers` in the *aoc_inputs*
+//! directory by default, with one `<day>:<part> <answer>` entry per line.
+//! Empty lines and lines starting with `#` are ignored.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{day_part_entries, Error, ParseError, ParseErrorKind, Part, PartAnswer};
+
+/// The recorded answers of each day and part.
+#[derive(Clone, Debug, Default)]
+pub struct ExpectedAnswers {
+    answers: HashMap<(u8, Part), String>,
+}
+
+impl ExpectedAnswers {
+    /// The expected answers file of provided inputs directory.
+    pub fn default_path(inputs_directory: &Path) -> PathBuf {
+        inputs_directory.join("expected_answers")
+    }
+
+    /// Load the expected answers from provided file, which must exist.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        Self::from_content(&fs::read_to_string(path)?)
+    }
+
+    /// Read the expected answers from the content of an expected answers file, where every entry
+    /// must have an answer.
+    pub fn from_content(content: &str) -> Result<Self, Error> {
+        let mut answers = HashMap::new();
+
+        for entry in day_part_entries(content) {
+            let entry = entry?;
+            if entry.value.is_empty() {
+                return Err(ParseError::new(
+                    None,
+                    entry.line,
+                    entry.value_column,
+                    entry.value,
+                    ParseErrorKind::InvalidFormat,
+                )
+                .into());
+            }
+
+            answers.insert((entry.day, entry.part), entry.value.to_string());
+        }
+
+        Ok(Self { answers })
+    }
+
+    /// The expected answer of provided day and part, as displayed.
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// The outcome of checking a single answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+/// The verdict given to the answer of a day part.
+#[derive(Clone, Debug)]
+pub struct PartVerification {
+    pub day: u8,
+    pub answer: PartAnswer,
+    pub verdict: Verdict,
+}
+
+impl PartVerification {
+    pub fn is_regression(&self) -> bool {
+        matches!(self.verdict, Verdict::Fail { .. })
+    }
+}
+
+/// Check the answers computed for a day against the expected ones.
+pub fn verify_answers(
+    expected_answers: &ExpectedAnswers,
+    day: u8,
+    part_answers: Vec<PartAnswer>,
+) -> Vec<PartVerification> {
+    part_answers
+        .into_iter()
+        .map(|answer| {
+            let verdict = match expected_answers.get(day, answer.part) {
+                None => Verdict::Missing,
+                Some(expected) if expected == answer.answer.to_string() => Verdict::Pass,
+                Some(expected) => Verdict::Fail {
+                    expected: expected.to_string(),
+                },
+            };
+
+            PartVerification {
+                day,
+                answer,
+                verdict,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_verify {
+    use super::{verify_answers, ExpectedAnswers, Verdict};
+    use crate::{expect_parse_error, ParseErrorKind, Part, PartAnswer};
+
+    const TEST_CONTENT: &str = "# day:part answer
+1:1 7
+1:2 5
+
+7:1 (2, 37)
+";
+
+    #[test]
+    fn test_load_expected_answers() {
+        let expected_answers = ExpectedAnswers::from_content(TEST_CONTENT).unwrap();
+
+        assert_eq!(expected_answers.get(1, Part::One), Some("7"));
+        assert_eq!(expected_answers.get(1, Part::Two), Some("5"));
+        assert_eq!(expected_answers.get(7, Part::One), Some("(2, 37)"));
+        assert_eq!(expected_answers.get(7, Part::Two), None);
+    }
+
+    #[test]
+    fn test_invalid_expected_answers() {
+        let err = expect_parse_error(ExpectedAnswers::from_content("1:1 7\n1:3 5"));
+        assert_eq!((err.day, err.line, err.column), (None, 2, 3));
+        assert_eq!(err.kind, ParseErrorKind::OutOfRange);
+
+        let err = expect_parse_error(ExpectedAnswers::from_content("1-1 7"));
+        assert_eq!(err.kind, ParseErrorKind::InvalidFormat);
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let expected_answers = ExpectedAnswers::from_content(TEST_CONTENT).unwrap();
+
+        let verifications = verify_answers(
+            &expected_answers,
+            1,
+            vec![
+                PartAnswer::new(Part::One, "Increases", 7),
+                PartAnswer::new(Part::Two, "Windowed increases", 6),
+            ],
+        );
+        assert_eq!(verifications[0].verdict, Verdict::Pass);
+        assert_eq!(
+            verifications[1].verdict,
+            Verdict::Fail {
+                expected: String::from("5")
+            }
+        );
+        assert!(verifications[1].is_regression());
+
+        let verifications = verify_answers(
+            &expected_answers,
+            7,
+            vec![
+                PartAnswer::new(Part::One, "Best position", (2, 37)),
+                PartAnswer::new(Part::Two, "New best position", (5, 168)),
+            ],
+        );
+        assert_eq!(verifications[0].verdict, Verdict::Pass);
+        assert_eq!(verifications[1].verdict, Verdict::Missing);
+    }
+}
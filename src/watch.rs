@@ -0,0 +1,168 @@
+//! This module detects changes of input files by polling, and compares the answers of
+//! successive runs.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::report::RunRecord;
+
+/// The state of a file at a given time, or None if it cannot be read.
+type FileStamp = Option<(SystemTime, u64)>;
+
+fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = fs::metadata(path).ok()?;
+
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Watch a set of files, reporting those which changed since the previous poll.
+/// A file is considered changed when its modification time or size changes, when it appears
+/// or when it disappears.
+#[derive(Clone, Debug)]
+pub struct FileWatcher {
+    stamps: HashMap<PathBuf, FileStamp>,
+}
+
+impl FileWatcher {
+    pub fn new<I>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = PathBuf>,
+    {
+        Self {
+            stamps: paths
+                .into_iter()
+                .map(|path| {
+                    let stamp = file_stamp(&path);
+                    (path, stamp)
+                })
+                .collect(),
+        }
+    }
+
+    /// The watched files which changed since the previous poll, sorted by path.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let mut changed_paths: Vec<PathBuf> = self
+            .stamps
+            .iter_mut()
+            .filter_map(|(path, stamp)| {
+                let new_stamp = file_stamp(path);
+                if new_stamp == *stamp {
+                    None
+                } else {
+                    *stamp = new_stamp;
+                    Some(path.clone())
+                }
+            })
+            .collect();
+        changed_paths.sort();
+
+        changed_paths
+    }
+}
+
+/// How the answer of a part evolved between two runs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AnswerChange {
+    /// The part was not run previously.
+    New,
+    Unchanged,
+    Changed {
+        previous: String,
+    },
+}
+
+/// Compare the records of a run with the records of the previous run of the same day and input.
+pub fn diff_records<'a>(
+    previous: &[RunRecord],
+    current: &'a [RunRecord],
+) -> Vec<(&'a RunRecord, AnswerChange)> {
+    current
+        .iter()
+        .map(|record| {
+            let change = match previous
+                .iter()
+                .find(|previous_record| previous_record.part == record.part)
+            {
+                None => AnswerChange::New,
+                Some(previous_record) => {
+                    let previous_answer = previous_record.answer_text();
+                    if previous_answer == record.answer_text() {
+                        AnswerChange::Unchanged
+                    } else {
+                        AnswerChange::Changed {
+                            previous: previous_answer,
+                        }
+                    }
+                }
+            };
+
+            (record, change)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_watch {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{diff_records, AnswerChange, FileWatcher};
+    use crate::input::InputSource;
+    use crate::report::RunRecord;
+    use crate::{Part, PartAnswer};
+
+    fn new_record(part: Part, answer: usize) -> RunRecord {
+        RunRecord {
+            day: 1,
+            part,
+            input: InputSource::File(PathBuf::from("aoc_inputs/daily_input_1")),
+            elapsed: Duration::ZERO,
+            outcome: Ok(PartAnswer::new(part, "Increases", answer)),
+        }
+    }
+
+    #[test]
+    fn test_diff_records() {
+        let previous = vec![new_record(Part::One, 7)];
+        let current = vec![new_record(Part::One, 7), new_record(Part::Two, 5)];
+
+        let changes: Vec<AnswerChange> = diff_records(&previous, &current)
+            .into_iter()
+            .map(|(_, change)| change)
+            .collect();
+        assert_eq!(changes, vec![AnswerChange::Unchanged, AnswerChange::New]);
+
+        let changes: Vec<AnswerChange> = diff_records(&current, &[new_record(Part::Two, 6)])
+            .into_iter()
+            .map(|(_, change)| change)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![AnswerChange::Changed {
+                previous: String::from("5")
+            }]
+        );
+    }
+
+    #[test]
+    fn test_file_watcher() {
+        let path = std::env::temp_dir().join(format!("aoc_watch_test_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut watcher = FileWatcher::new([path.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::write(&path, "199\n").unwrap();
+        assert_eq!(watcher.poll(), vec![path.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::write(&path, "199\n200\n").unwrap();
+        assert_eq!(watcher.poll(), vec![path.clone()]);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.poll(), vec![path]);
+    }
+}
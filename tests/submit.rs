@@ -0,0 +1,68 @@
+//! Round trips between the submission client and the stand-in server of the `mock_server`
+//! example.
+
+#![deny(clippy::all)]
+
+#[path = "../examples/mock_server/server.rs"]
+mod server;
+
+use std::thread;
+use std::time::Duration;
+
+use aoc_2021_rs::submit::{submit_answer, Endpoint, SubmitOutcome};
+use aoc_2021_rs::verify::ExpectedAnswers;
+use aoc_2021_rs::Part;
+use server::MockServer;
+
+#[test]
+fn test_submit_to_mock_server() {
+    let expected_answers = ExpectedAnswers::from_content("1:1 7\n7:1 (2, 37)").unwrap();
+    let server = MockServer::bind("127.0.0.1:0", expected_answers).unwrap();
+    let endpoint = format!("http://{}", server.local_addr().unwrap())
+        .parse::<Endpoint>()
+        .unwrap();
+    let handle = thread::spawn(move || server.serve(Some(4)));
+
+    let outcomes: Vec<SubmitOutcome> = [(1, "9"), (1, "5"), (1, "7"), (7, "(2, 37)")]
+        .into_iter()
+        .map(|(day, answer)| submit_answer(&endpoint, day, Part::One, answer).unwrap())
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            SubmitOutcome::TooHigh,
+            SubmitOutcome::TooLow,
+            SubmitOutcome::Correct,
+            SubmitOutcome::Correct
+        ]
+    );
+    handle.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_rate_limited_mock_server() {
+    let expected_answers = ExpectedAnswers::from_content("1:1 7").unwrap();
+    let server = MockServer::bind("127.0.0.1:0", expected_answers)
+        .unwrap()
+        .with_min_interval(Duration::from_secs(60));
+    let endpoint = format!("http://{}", server.local_addr().unwrap())
+        .parse::<Endpoint>()
+        .unwrap();
+    let handle = thread::spawn(move || server.serve(Some(2)));
+
+    assert_eq!(
+        submit_answer(&endpoint, 1, Part::One, "8").unwrap(),
+        SubmitOutcome::TooHigh
+    );
+    assert!(matches!(
+        submit_answer(&endpoint, 1, Part::One, "7").unwrap(),
+        SubmitOutcome::RateLimited { wait: Some(_) }
+    ));
+    handle.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_form_decode() {
+    assert_eq!(server::form_decode("%282%2C+37%29"), "(2, 37)");
+    assert_eq!(server::form_decode("100%"), "100%");
+}